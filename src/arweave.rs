@@ -0,0 +1,72 @@
+use url::Url;
+
+use crate::{ipfs::join_gateway, Metadata};
+
+/// The scheme of Arweave URLs, such as `ar://<txid>`.
+const SCHEME: &str = "ar://";
+
+/// Rewrites an `ar://<txid>/<path>` URL to `<gateway>/<txid>/<path>`.
+///
+/// Returns `None` if `uri` is not an Arweave URL.
+pub fn arweave_to_gateway(uri: &str, gateway: &Url) -> Option<Result<Url, url::ParseError>> {
+    let path = uri.strip_prefix(SCHEME)?;
+    Some(join_gateway(gateway, path))
+}
+
+impl Metadata {
+    /// Returns a copy of the metadata with every `ar://` URL rewritten to use an HTTP `gateway`, such as
+    /// `https://arweave.net/`.
+    ///
+    /// This covers the same fields as [`Metadata::to_gateway`]. See [`arweave_to_gateway`].
+    pub fn to_arweave_gateway(&self, gateway: &Url) -> Metadata {
+        self.rewrite_urls(|url| arweave_to_gateway(url, gateway).and_then(Result::ok))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::{Metadata, RawUrl};
+
+    const TXID: &str = "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U";
+
+    #[test]
+    fn gateway() {
+        let metadata = Metadata {
+            image: Some(RawUrl(format!("ar://{}", TXID))),
+            animation_url: Some(RawUrl(format!("ar://{}/0.mp4", TXID))),
+            external_url: Some(Url::parse(&format!("ar://{}", TXID)).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            metadata.external_url.as_ref().unwrap().as_str(),
+            format!("ar://{}", TXID)
+        );
+
+        let metadata = metadata.to_arweave_gateway(&Url::parse("https://arweave.net/").unwrap());
+        assert_eq!(
+            metadata.image,
+            Some(RawUrl(format!("https://arweave.net/{}", TXID)))
+        );
+        assert_eq!(
+            metadata.animation_url,
+            Some(RawUrl(format!("https://arweave.net/{}/0.mp4", TXID)))
+        );
+        assert_eq!(
+            metadata.external_url.unwrap().as_str(),
+            format!("https://arweave.net/{}", TXID)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn round_trip() {
+        let s = format!(
+            r#"{{"name":"Arweave","description":"Stored permanently","image":"ar://{0}","external_url":"ar://{0}","animation_url":"ar://{0}/0.mp4"}}"#,
+            TXID
+        );
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(serde_json::to_string(&metadata).unwrap(), s);
+    }
+}
@@ -0,0 +1,207 @@
+//! Borsh encodings for fields whose types do not implement the Borsh traits, for use with
+//! `#[borsh(serialize_with = "...", deserialize_with = "...")]`.
+//!
+//! The `borsh` feature enables `serde`, so that fields which only exist with it, such as
+//! [`Metadata::extra`](crate::Metadata::extra), are always part of the encoding.
+
+use borsh::{
+    io::{Error, ErrorKind, Read, Write},
+    BorshDeserialize, BorshSerialize,
+};
+
+/// Encodes a [`Url`](url::Url) as its string form.
+pub(crate) mod url {
+    use ::url::Url;
+
+    use super::*;
+
+    pub(crate) fn serialize<W: Write>(url: &Url, writer: &mut W) -> Result<(), Error> {
+        url.as_str().serialize(writer)
+    }
+
+    pub(crate) fn deserialize<R: Read>(reader: &mut R) -> Result<Url, Error> {
+        Url::parse(&String::deserialize_reader(reader)?)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+/// Encodes an optional [`Url`](url::Url) as an optional string.
+pub(crate) mod option_url {
+    use ::url::Url;
+
+    use super::*;
+
+    pub(crate) fn serialize<W: Write>(url: &Option<Url>, writer: &mut W) -> Result<(), Error> {
+        url.as_ref().map(Url::as_str).serialize(writer)
+    }
+
+    pub(crate) fn deserialize<R: Read>(reader: &mut R) -> Result<Option<Url>, Error> {
+        Option::<String>::deserialize_reader(reader)?
+            .map(|url| Url::parse(&url).map_err(|e| Error::new(ErrorKind::InvalidData, e)))
+            .transpose()
+    }
+}
+
+/// Encodes an [`RGB8`](rgb::RGB8) as three bytes.
+pub(crate) mod rgb8 {
+    use ::rgb::RGB8;
+
+    use super::*;
+
+    pub(crate) fn serialize<W: Write>(color: &RGB8, writer: &mut W) -> Result<(), Error> {
+        [color.r, color.g, color.b].serialize(writer)
+    }
+
+    pub(crate) fn deserialize<R: Read>(reader: &mut R) -> Result<RGB8, Error> {
+        let [r, g, b] = <[u8; 3]>::deserialize_reader(reader)?;
+        Ok(RGB8 { r, g, b })
+    }
+}
+
+/// Encodes an [`OrderedFloat`](ordered_float::OrderedFloat) as the underlying [`f64`].
+pub(crate) mod ordered_float {
+    use ::ordered_float::OrderedFloat;
+
+    use super::*;
+
+    pub(crate) fn serialize<W: Write>(
+        value: &OrderedFloat<f64>,
+        writer: &mut W,
+    ) -> Result<(), Error> {
+        value.0.serialize(writer)
+    }
+
+    pub(crate) fn deserialize<R: Read>(reader: &mut R) -> Result<OrderedFloat<f64>, Error> {
+        f64::deserialize_reader(reader).map(OrderedFloat)
+    }
+}
+
+/// Encodes free-form JSON, such as [`Metadata::extra`](crate::Metadata::extra), as a JSON string.
+pub(crate) mod json {
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+
+    pub(crate) fn serialize<T: serde::Serialize, W: Write>(
+        value: &T,
+        writer: &mut W,
+    ) -> Result<(), Error> {
+        serde_json::to_string(value)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?
+            .serialize(writer)
+    }
+
+    pub(crate) fn deserialize<T: DeserializeOwned, R: Read>(reader: &mut R) -> Result<T, Error> {
+        serde_json::from_str(&String::deserialize_reader(reader)?)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rgb::RGB8;
+    use url::Url;
+
+    use crate::{AttributeEntry, BackgroundColor, CollectionInfo, DisplayType, Metadata, Royalty};
+
+    fn metadata() -> Metadata {
+        Metadata {
+            external_url: Some(Url::parse("https://wanderers.ai/").unwrap()),
+            attributes: vec![
+                AttributeEntry::string("Core", "Vortex"),
+                AttributeEntry::number("Level", 3),
+                AttributeEntry {
+                    display_type: Some(DisplayType::BoostPercentage),
+                    ..AttributeEntry::float("Stamina Increase", 1.5)
+                },
+                AttributeEntry::new("Ringed", true),
+            ],
+            background_color: Some(BackgroundColor(RGB8::new(0xf2, 0xf2, 0xf2))),
+            collection: Some(CollectionInfo::new("Planet Pass")),
+            royalties: vec![Royalty::new(
+                "0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6",
+                250,
+            )],
+            seller_fee_basis_points: Some(250),
+            ..crate::fixtures::metadata()
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let metadata = metadata();
+        let bytes = borsh::to_vec(&metadata).unwrap();
+        assert_eq!(borsh::from_slice::<Metadata>(&bytes).unwrap(), metadata);
+    }
+
+    #[test]
+    fn metadata_bytes() {
+        let mut metadata = Metadata {
+            name: "A".into(),
+            description: "B".into(),
+            image: None,
+            attributes: vec![AttributeEntry::new("C", 1)],
+            ..Default::default()
+        };
+        metadata.extra.insert("d".into(), serde_json::json!(2));
+
+        let attribute: &[u8] = &[
+            1, 1, 0, 0, 0, b'C', // trait_type
+            2, 1, 0, 0, 0, 0, 0, 0, 0, // value
+            0, // max_value
+            0, // display_type
+            2, 0, 0, 0, b'{', b'}', // extra
+        ];
+        let expected = [
+            &[1, 0, 0, 0, b'A'][..], // name
+            &[1, 0, 0, 0, b'B'],     // description
+            &[0, 0, 0],              // image, image_data, external_url
+            &[1, 0, 0, 0],           // attributes
+            attribute,
+            &[0, 0, 0, 0, 0], // background_color, background_color_alpha, animation_url, youtube_url, collection
+            &[0, 0, 0, 0],    // royalties
+            &[0, 0, 0],       // seller_fee_basis_points, fee_recipient, properties
+            &[7, 0, 0, 0],    // extra
+            br#"{"d":2}"#,
+        ]
+        .concat();
+        assert_eq!(borsh::to_vec(&metadata).unwrap(), expected);
+        assert_eq!(borsh::from_slice::<Metadata>(&expected).unwrap(), metadata);
+    }
+
+    #[test]
+    fn background_color_bytes() {
+        let color = BackgroundColor(RGB8::new(0xf2, 0x00, 0x0a));
+        assert_eq!(borsh::to_vec(&color).unwrap(), [0xf2, 0x00, 0x0a]);
+    }
+
+    #[test]
+    fn invalid_url() {
+        let mut bytes = borsh::to_vec(&Metadata {
+            external_url: Some(Url::parse("https://a/").unwrap()),
+            ..Default::default()
+        })
+        .unwrap();
+        let position = bytes.windows(10).position(|w| w == b"https://a/").unwrap();
+        bytes[position + 5] = b'!';
+        assert!(borsh::from_slice::<Metadata>(&bytes).is_err());
+    }
+
+    #[test]
+    fn planetpass_round_trip() {
+        let metadata = serde_json::from_str::<Metadata>(crate::tests::PLANETPASS_ITEM).unwrap();
+        let bytes = borsh::to_vec(&metadata).unwrap();
+        assert_eq!(borsh::from_slice::<Metadata>(&bytes).unwrap(), metadata);
+    }
+
+    #[test]
+    fn extra_round_trip() {
+        let mut metadata = metadata();
+        metadata
+            .extra
+            .insert("tags".into(), serde_json::json!(["art", 1]));
+        metadata.properties = Some(serde_json::from_str(r#"{ "generation": 2 }"#).unwrap());
+        let bytes = borsh::to_vec(&metadata).unwrap();
+        assert_eq!(borsh::from_slice::<Metadata>(&bytes).unwrap(), metadata);
+    }
+}
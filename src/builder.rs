@@ -0,0 +1,458 @@
+use std::fmt;
+
+use url::Url;
+
+use crate::{
+    AttributeEntry, BackgroundColor, CollectionInfo, ColorError, Metadata, RawUrl, Royalty,
+};
+
+/// Conversion into a [`Url`], used by the URL setters of [`MetadataBuilder`].
+///
+/// Parsing failures are deferred until [`MetadataBuilder::build`].
+pub trait IntoUrl {
+    /// Converts `self` into a [`Url`].
+    fn into_url(self) -> Result<Url, url::ParseError>;
+}
+
+impl IntoUrl for Url {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Ok(self)
+    }
+}
+
+impl IntoUrl for &Url {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Ok(self.clone())
+    }
+}
+
+impl IntoUrl for &str {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Url::parse(self)
+    }
+}
+
+impl IntoUrl for String {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Url::parse(&self)
+    }
+}
+
+impl IntoUrl for &String {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Url::parse(self)
+    }
+}
+
+/// Incremental builder for [`Metadata`].
+///
+/// Created with [`Metadata::builder`] or [`MetadataBuilder::with_required`]. The required fields
+/// ([`image`](MetadataBuilder::image), [`name`](MetadataBuilder::name) and
+/// [`description`](MetadataBuilder::description)) are checked when calling
+/// [`build`](MetadataBuilder::build), along with any URL that failed to parse and was not set again since.
+#[derive(Debug, Clone, Default)]
+pub struct MetadataBuilder {
+    /// URL fields that failed to parse, in the order they were set.
+    errors: Vec<(&'static str, url::ParseError)>,
+    image: Option<RawUrl>,
+    image_data: Option<String>,
+    external_url: Option<Url>,
+    description: Option<String>,
+    name: Option<String>,
+    attributes: Vec<AttributeEntry>,
+    background_color: Option<BackgroundColor>,
+    background_color_alpha: Option<u8>,
+    animation_url: Option<RawUrl>,
+    youtube_url: Option<Url>,
+    collection: Option<CollectionInfo>,
+    royalties: Vec<Royalty>,
+    seller_fee_basis_points: Option<u32>,
+    fee_recipient: Option<String>,
+    #[cfg(feature = "serde")]
+    properties: Option<crate::Properties>,
+}
+
+impl MetadataBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a builder with the required fields already set.
+    pub fn with_required(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        image: impl IntoUrl,
+    ) -> Self {
+        Self::new().name(name).description(description).image(image)
+    }
+
+    /// Parses a URL for `field`, replacing any earlier parse failure of the field.
+    fn url(&mut self, field: &'static str, url: impl IntoUrl) -> Option<Url> {
+        self.errors.retain(|(failed, _)| *failed != field);
+        match url.into_url() {
+            Ok(url) => Some(url),
+            Err(source) => {
+                self.errors.push((field, source));
+                None
+            }
+        }
+    }
+
+    /// Sets the URL to the image of the item.
+    pub fn image(mut self, image: impl IntoUrl) -> Self {
+        self.image = self.url("image", image).map(RawUrl::from);
+        self
+    }
+
+    /// Sets the URL to the image of the item without parsing it, so that it may be relative, such as
+    /// `"0.png"`. See [`Metadata::resolve_urls`].
+    pub fn image_raw(mut self, image: impl Into<RawUrl>) -> Self {
+        self.errors.retain(|(failed, _)| *failed != "image");
+        self.image = Some(image.into());
+        self
+    }
+
+    /// Sets the raw SVG image data of the item.
+    pub fn image_data(mut self, image_data: impl Into<String>) -> Self {
+        self.image_data = Some(image_data.into());
+        self
+    }
+
+    /// Sets the external URL to another site.
+    pub fn external_url(mut self, external_url: impl IntoUrl) -> Self {
+        self.external_url = self.url("external_url", external_url);
+        self
+    }
+
+    /// Sets the human-readable description of the item.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the name of the item.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Appends an attribute to the item.
+    pub fn attribute(mut self, attribute: AttributeEntry) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Appends several attributes to the item.
+    pub fn attributes(mut self, attributes: impl IntoIterator<Item = AttributeEntry>) -> Self {
+        self.attributes.extend(attributes);
+        self
+    }
+
+    /// Sets the background color of the item.
+    pub fn background_color(mut self, background_color: impl Into<BackgroundColor>) -> Self {
+        self.background_color = Some(background_color.into());
+        self
+    }
+
+    /// Sets the alpha channel of the background color, where `0` is fully transparent.
+    pub fn background_color_alpha(mut self, alpha: u8) -> Self {
+        self.background_color_alpha = Some(alpha);
+        self
+    }
+
+    /// Sets the background color of the item from a string such as `"#1e90ff"`, accepting the same forms as
+    /// [`BackgroundColor::parse_with_alpha`]. The alpha channel is set from an 8-digit color, and cleared
+    /// otherwise.
+    pub fn background_color_hex(mut self, background_color: &str) -> Result<Self, ColorError> {
+        let (color, alpha) = BackgroundColor::parse_with_alpha(background_color)?;
+        self.background_color = Some(color);
+        self.background_color_alpha = alpha;
+        Ok(self)
+    }
+
+    /// Sets the URL to a multi-media attachment for the item.
+    pub fn animation_url(mut self, animation_url: impl IntoUrl) -> Self {
+        self.animation_url = self.url("animation_url", animation_url).map(RawUrl::from);
+        self
+    }
+
+    /// Sets the URL to a multi-media attachment for the item without parsing it, so that it may be relative.
+    /// See [`Metadata::resolve_urls`].
+    pub fn animation_url_raw(mut self, animation_url: impl Into<RawUrl>) -> Self {
+        self.errors.retain(|(failed, _)| *failed != "animation_url");
+        self.animation_url = Some(animation_url.into());
+        self
+    }
+
+    /// Sets the URL to a YouTube video.
+    pub fn youtube_url(mut self, youtube_url: impl IntoUrl) -> Self {
+        self.youtube_url = self.url("youtube_url", youtube_url);
+        self
+    }
+
+    /// Sets the collection the item belongs to.
+    pub fn collection(mut self, collection: CollectionInfo) -> Self {
+        self.collection = Some(collection);
+        self
+    }
+
+    /// Appends a royalty paid on secondary sales.
+    pub fn royalty(mut self, royalty: Royalty) -> Self {
+        self.royalties.push(royalty);
+        self
+    }
+
+    /// Sets the royalty hint for marketplaces, in basis points.
+    pub fn seller_fee_basis_points(mut self, seller_fee_basis_points: u32) -> Self {
+        self.seller_fee_basis_points = Some(seller_fee_basis_points);
+        self
+    }
+
+    /// Sets the address that the royalty hint is paid to.
+    pub fn fee_recipient(mut self, fee_recipient: impl Into<String>) -> Self {
+        self.fee_recipient = Some(fee_recipient.into());
+        self
+    }
+
+    /// Sets the free-form ERC-1155 properties of the item.
+    #[cfg(feature = "serde")]
+    pub fn properties(mut self, properties: impl Into<crate::Properties>) -> Self {
+        self.properties = Some(properties.into());
+        self
+    }
+
+    /// Builds the [`Metadata`], failing if any required field was never set or any URL failed to parse.
+    pub fn build(self) -> Result<Metadata, BuilderError> {
+        if let Some((field, source)) = self.errors.into_iter().next() {
+            return Err(BuilderError::InvalidUrl { field, source });
+        }
+        if self.image.is_none() && self.image_data.is_none() {
+            return Err(BuilderError::MissingField("image"));
+        }
+        Ok(Metadata {
+            image: self.image,
+            image_data: self.image_data,
+            external_url: self.external_url,
+            description: self
+                .description
+                .ok_or(BuilderError::MissingField("description"))?,
+            name: self.name.ok_or(BuilderError::MissingField("name"))?,
+            attributes: self.attributes,
+            background_color: self.background_color,
+            background_color_alpha: self.background_color_alpha,
+            animation_url: self.animation_url,
+            youtube_url: self.youtube_url,
+            collection: self.collection,
+            royalties: self.royalties,
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            fee_recipient: self.fee_recipient,
+            #[cfg(feature = "serde")]
+            properties: self.properties,
+            #[cfg(feature = "serde")]
+            extra: Default::default(),
+        })
+    }
+}
+
+/// Error returned by [`MetadataBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuilderError {
+    /// A required field was never set.
+    MissingField(&'static str),
+    /// A URL field could not be parsed.
+    InvalidUrl {
+        /// Name of the field.
+        field: &'static str,
+        /// Underlying parse error.
+        source: url::ParseError,
+    },
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::MissingField(field) => write!(f, "missing required field `{}`", field),
+            BuilderError::InvalidUrl { field, source } => {
+                write!(f, "invalid URL in field `{}`: {}", field, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuilderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuilderError::MissingField(_) => None,
+            BuilderError::InvalidUrl { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rgb::RGB8;
+    use url::Url;
+
+    use crate::{
+        fixtures, AttributeEntry, BackgroundColor, BuilderError, ColorError, Metadata,
+        MetadataBuilder, RawUrl,
+    };
+
+    #[test]
+    fn build() {
+        let metadata = Metadata::builder()
+            .image(Url::parse(fixtures::IMAGE).unwrap())
+            .name(fixtures::NAME)
+            .description(fixtures::DESCRIPTION)
+            .background_color(RGB8 {
+                r: 242,
+                g: 242,
+                b: 242,
+            })
+            .attribute(AttributeEntry::string("Core", "Vortex"))
+            .attribute(AttributeEntry::string("Satellite", "Protoplanets"))
+            .build()
+            .unwrap();
+
+        assert_eq!(metadata.name, fixtures::NAME);
+        assert_eq!(metadata.attributes.len(), 2);
+        assert!(metadata.external_url.is_none());
+    }
+
+    #[test]
+    fn relative_urls() {
+        let mut metadata = Metadata::builder()
+            .name(fixtures::NAME)
+            .description(fixtures::DESCRIPTION)
+            .image_raw("0.png")
+            .animation_url_raw(String::from("vid/0.mp4"))
+            .build()
+            .unwrap();
+        assert_eq!(metadata.image, Some(RawUrl::from("0.png")));
+
+        let base = Url::parse("https://assets.wanderers.ai/file/planetpass/").unwrap();
+        metadata.resolve_urls(&base).unwrap();
+        assert_eq!(
+            metadata.image,
+            Some(RawUrl::from(
+                "https://assets.wanderers.ai/file/planetpass/0.png"
+            ))
+        );
+        assert_eq!(
+            metadata.animation_url,
+            Some(RawUrl::from(
+                "https://assets.wanderers.ai/file/planetpass/vid/0.mp4"
+            ))
+        );
+    }
+
+    #[test]
+    fn background_color_hex() {
+        let metadata = fixtures::builder()
+            .background_color_hex("#1e90ff")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            metadata.background_color,
+            Some(BackgroundColor::from_rgb8(RGB8::new(0x1e, 0x90, 0xff)))
+        );
+        assert_eq!(metadata.background_color_alpha, None);
+
+        let metadata = Metadata::builder()
+            .name(fixtures::NAME)
+            .description(fixtures::DESCRIPTION)
+            .image(fixtures::IMAGE)
+            .background_color_hex("#1e90ff80")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            metadata.background_color,
+            Some(BackgroundColor::from_rgb8(RGB8::new(0x1e, 0x90, 0xff)))
+        );
+        assert_eq!(metadata.background_color_alpha, Some(0x80));
+
+        assert_eq!(
+            Metadata::builder().background_color_hex("#1e90f").err(),
+            Some(ColorError::InvalidLength(5))
+        );
+    }
+
+    #[test]
+    fn missing_field() {
+        let result = Metadata::builder()
+            .name(fixtures::NAME)
+            .description(fixtures::DESCRIPTION)
+            .build();
+        assert_eq!(result, Err(BuilderError::MissingField("image")));
+
+        let metadata = Metadata::builder()
+            .name("Bag #1")
+            .description("Loot is randomized adventurer gear generated and stored on chain.")
+            .image_data("<svg></svg>")
+            .build()
+            .unwrap();
+        assert_eq!(metadata.image, None);
+    }
+
+    #[test]
+    fn with_required() {
+        let metadata =
+            MetadataBuilder::with_required(fixtures::NAME, fixtures::DESCRIPTION, fixtures::IMAGE)
+                .external_url("https://wanderers.ai")
+                .animation_url(fixtures::IMAGE)
+                .attributes(
+                    [
+                        ("Core", "Vortex"),
+                        ("Satellite", "Protoplanets"),
+                        ("Feature", "Icy"),
+                    ]
+                    .map(|(trait_type, value)| AttributeEntry::string(trait_type, value)),
+                )
+                .build()
+                .unwrap();
+
+        assert_eq!(
+            metadata.external_url,
+            Some(Url::parse("https://wanderers.ai").unwrap())
+        );
+        assert_eq!(metadata.attributes.len(), 3);
+    }
+
+    #[test]
+    fn invalid_url() {
+        let result =
+            MetadataBuilder::with_required(fixtures::NAME, fixtures::DESCRIPTION, "not a url")
+                .youtube_url("also not a url")
+                .build();
+        assert_eq!(
+            result,
+            Err(BuilderError::InvalidUrl {
+                field: "image",
+                source: url::ParseError::RelativeUrlWithoutBase,
+            })
+        );
+    }
+
+    #[test]
+    fn overwritten_invalid_url() {
+        let builder =
+            MetadataBuilder::with_required(fixtures::NAME, fixtures::DESCRIPTION, "not a url");
+        assert!(builder.clone().image(fixtures::IMAGE).build().is_ok());
+        assert!(builder.clone().image_raw("0.png").build().is_ok());
+
+        let builder = builder
+            .animation_url("not a url either")
+            .image(fixtures::IMAGE);
+        assert_eq!(
+            builder.clone().build(),
+            Err(BuilderError::InvalidUrl {
+                field: "animation_url",
+                source: url::ParseError::RelativeUrlWithoutBase,
+            })
+        );
+        assert!(builder.animation_url_raw("0.mp4").build().is_ok());
+    }
+}
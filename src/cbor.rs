@@ -0,0 +1,82 @@
+use crate::Metadata;
+
+impl Metadata {
+    /// Serializes the metadata to [CBOR](https://cbor.io/), with the same field names and values as the JSON
+    /// form.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, ciborium::ser::Error<std::io::Error>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Deserializes metadata from CBOR, accepting everything the JSON form accepts.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Metadata, ciborium::de::Error<std::io::Error>> {
+        ciborium::from_reader(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ciborium::Value;
+    use rgb::RGB8;
+
+    use crate::{AttributeEntry, AttributeValue, BackgroundColor, DisplayType, Metadata};
+
+    #[test]
+    fn round_trip() {
+        let metadata = serde_json::from_str::<Metadata>(crate::tests::PLANETPASS_ITEM).unwrap();
+        let bytes = metadata.to_cbor().unwrap();
+        assert_eq!(Metadata::from_cbor(&bytes).unwrap(), metadata);
+    }
+
+    #[test]
+    fn attribute_values() {
+        let mut metadata = serde_json::from_str::<Metadata>(crate::tests::PLANETPASS_ITEM).unwrap();
+        metadata.attributes = vec![
+            AttributeEntry::new("Ringed", true),
+            AttributeEntry::string("Core", "Vortex"),
+            AttributeEntry::number("Level", -3),
+            AttributeEntry::number("Supply", i64::MAX),
+            AttributeEntry {
+                display_type: Some(DisplayType::BoostPercentage),
+                ..AttributeEntry::float("Stamina Increase", 1.5)
+            },
+            AttributeEntry::float("Mass", 0.1),
+        ];
+        metadata.background_color = Some(BackgroundColor::from_rgb8(RGB8::new(0xf2, 0x00, 0x0a)));
+        metadata
+            .extra
+            .insert("tags".into(), serde_json::json!(["art", 1, null]));
+
+        let bytes = metadata.to_cbor().unwrap();
+        let round_trip = Metadata::from_cbor(&bytes).unwrap();
+        assert_eq!(round_trip, metadata);
+        assert_eq!(round_trip.attributes[2].value, AttributeValue::Integer(-3));
+        assert_eq!(
+            round_trip.attributes[5].value,
+            AttributeValue::Float(0.1.into())
+        );
+    }
+
+    #[test]
+    fn background_color_is_hex() {
+        let metadata = Metadata {
+            background_color: Some(BackgroundColor::from_rgb8(RGB8::new(0xf2, 0xf2, 0xf2))),
+            ..Default::default()
+        };
+        let value = ciborium::from_reader::<Value, _>(&metadata.to_cbor().unwrap()[..]).unwrap();
+        let color = value
+            .as_map()
+            .unwrap()
+            .iter()
+            .find(|(key, _)| key.as_text() == Some("background_color"))
+            .map(|(_, value)| value.clone());
+        assert_eq!(color, Some(Value::Text("f2f2f2".into())));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Metadata::from_cbor(&[0xa0]).is_err());
+        assert!(Metadata::from_cbor(&[]).is_err());
+    }
+}
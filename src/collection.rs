@@ -0,0 +1,259 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Metadata;
+
+/// The collection an item belongs to, as read by wallets and the legacy OpenSea schema.
+///
+/// When deserializing, a bare string is also accepted and treated as the [`name`](CollectionInfo::name).
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "CollectionRepr")
+)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CollectionInfo {
+    /// Name of the collection.
+    pub name: String,
+    /// Family of related collections that the collection belongs to.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub family: Option<String>,
+}
+
+impl CollectionInfo {
+    /// Creates a collection with no family.
+    pub fn new(name: impl Into<String>) -> Self {
+        CollectionInfo {
+            name: name.into(),
+            family: None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CollectionRepr {
+    Name(String),
+    Object {
+        name: String,
+        #[serde(default)]
+        family: Option<String>,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl From<CollectionRepr> for CollectionInfo {
+    fn from(repr: CollectionRepr) -> Self {
+        match repr {
+            CollectionRepr::Name(name) => CollectionInfo::new(name),
+            CollectionRepr::Object { name, family } => CollectionInfo { name, family },
+        }
+    }
+}
+
+/// The metadata of every token in a drop, used to compute trait rarity.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Collection(pub Vec<Metadata>);
+
+impl Collection {
+    /// Counts the items having each `(trait_type, value)` pair, with the value formatted by its
+    /// [`Display`](std::fmt::Display) implementation.
+    ///
+    /// An item repeating the same pair is counted once, and generic attributes are ignored.
+    pub fn trait_frequencies(&self) -> HashMap<(String, String), usize> {
+        let mut frequencies = HashMap::new();
+        for item in &self.0 {
+            for (trait_type, value) in traits(item) {
+                *frequencies.entry((trait_type, value)).or_insert(0) += 1;
+            }
+        }
+        frequencies
+    }
+
+    /// Counts the items having each value of `trait_type`, with the value formatted by its
+    /// [`Display`](std::fmt::Display) implementation, so `5` and `5.5` become `"5"` and `"5.5"`.
+    ///
+    /// An item with several attributes of the same trait type is counted once for each distinct value.
+    pub fn distribution(&self, trait_type: &str) -> BTreeMap<String, usize> {
+        let mut distribution = BTreeMap::new();
+        for item in &self.0 {
+            let values = traits(item)
+                .into_iter()
+                .filter(|(t, _)| t == trait_type)
+                .map(|(_, value)| value);
+            for value in values {
+                *distribution.entry(value).or_insert(0) += 1;
+            }
+        }
+        distribution
+    }
+
+    /// Scores the rarity of `item` as the sum of `1 / frequency` over its distinct traits, where a higher score
+    /// is rarer.
+    ///
+    /// Traits that appear nowhere in the collection are ignored. The frequencies are recomputed on every call,
+    /// so use [`trait_frequencies`](Collection::trait_frequencies) directly when ranking a large collection.
+    pub fn rarity_score(&self, item: &Metadata) -> f64 {
+        let frequencies = self.trait_frequencies();
+        traits(item)
+            .into_iter()
+            .filter_map(|key| frequencies.get(&key))
+            .map(|&frequency| 1.0 / frequency as f64)
+            .sum()
+    }
+}
+
+impl From<Vec<Metadata>> for Collection {
+    fn from(items: Vec<Metadata>) -> Self {
+        Collection(items)
+    }
+}
+
+/// Returns the distinct `(trait_type, value)` pairs of `item`, skipping generic attributes.
+fn traits(item: &Metadata) -> BTreeSet<(String, String)> {
+    item.attributes
+        .iter()
+        .filter_map(|attribute| {
+            Some((
+                attribute.trait_type()?.to_owned(),
+                attribute.value.to_string(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    use serde_json::json;
+
+    #[cfg(feature = "serde")]
+    use crate::CollectionInfo;
+    use crate::{AttributeEntry, Collection, Metadata};
+
+    fn item(core: &str, level: i64) -> Metadata {
+        Metadata {
+            attributes: vec![
+                AttributeEntry::string("Core", core),
+                AttributeEntry::number("Level", level),
+            ],
+            ..crate::fixtures::metadata()
+        }
+    }
+
+    #[test]
+    fn rarity() {
+        let collection = Collection(vec![
+            item("Vortex", 1),
+            item("Vortex", 1),
+            item("Nebula", 3),
+        ]);
+        let frequencies = collection.trait_frequencies();
+        assert_eq!(frequencies[&("Core".into(), "Vortex".into())], 2);
+        assert_eq!(frequencies[&("Level".into(), "3".into())], 1);
+
+        let common = collection.rarity_score(&collection.0[0]);
+        let rare = collection.rarity_score(&collection.0[2]);
+        assert_eq!(common, collection.rarity_score(&collection.0[1]));
+        assert_eq!(common, 1.0);
+        assert_eq!(rare, 2.0);
+        assert!(rare > common);
+    }
+
+    #[test]
+    fn duplicated_attribute() {
+        let mut repeated = item("Vortex", 1);
+        repeated
+            .attributes
+            .push(AttributeEntry::string("Core", "Vortex"));
+        let collection = Collection(vec![repeated, item("Nebula", 1)]);
+        assert_eq!(
+            collection.trait_frequencies()[&("Core".into(), "Vortex".into())],
+            1
+        );
+        assert_eq!(collection.distribution("Core")["Vortex"], 1);
+        assert_eq!(collection.rarity_score(&collection.0[0]), 1.5);
+    }
+
+    #[test]
+    fn distribution() {
+        let collection = Collection(vec![
+            item("Vortex", 1),
+            item("Nebula", 10),
+            item("Vortex", 2),
+            item("Vortex", 10),
+        ]);
+        assert_eq!(
+            collection
+                .distribution("Core")
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [("Nebula".into(), 1), ("Vortex".into(), 3)]
+        );
+        assert_eq!(
+            collection
+                .distribution("Level")
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [("1".into(), 1), ("10".into(), 2), ("2".into(), 1)]
+        );
+        assert!(collection.distribution("Eyes").is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    fn metadata(collection: Option<serde_json::Value>) -> serde_json::Value {
+        let mut value = crate::fixtures::metadata_json();
+        if let Some(collection) = collection {
+            value["collection"] = collection;
+        }
+        value
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn object() {
+        let value = metadata(Some(json!({ "name": "Solflare X", "family": "Solflare" })));
+        let metadata = serde_json::from_value::<Metadata>(value.clone()).unwrap();
+        assert_eq!(
+            metadata.collection,
+            Some(CollectionInfo {
+                name: "Solflare X".into(),
+                family: Some("Solflare".into()),
+            })
+        );
+        assert_eq!(serde_json::to_value(&metadata).unwrap(), value);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn string() {
+        let metadata =
+            serde_json::from_value::<Metadata>(metadata(Some(json!("Solflare X")))).unwrap();
+        assert_eq!(metadata.collection, Some(CollectionInfo::new("Solflare X")));
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap()["collection"],
+            json!({ "name": "Solflare X" })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn absent() {
+        let value = metadata(None);
+        let metadata = serde_json::from_value::<Metadata>(value.clone()).unwrap();
+        assert_eq!(metadata.collection, None);
+        assert_eq!(serde_json::to_value(&metadata).unwrap(), value);
+    }
+}
@@ -0,0 +1,844 @@
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use rgb::{RGB8, RGBA8};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Parses a hexadecimal color string into an [`RGB8`].
+///
+/// The string may have a single leading `#` and surrounding whitespace, and must contain either 6 hexadecimal
+/// digits (`f2f2f2`) or 3 shorthand digits (`fff`, which is expanded to `ffffff`).
+pub fn parse_hex_color(s: &str) -> Result<RGB8, ColorError> {
+    let s = s.trim();
+    let s = s.strip_prefix('#').unwrap_or(s);
+    let digits = s
+        .chars()
+        .map(|c| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(ColorError::InvalidHexDigit(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match digits[..] {
+        [r, g, b] => Ok(RGB8 {
+            r: r * 0x11,
+            g: g * 0x11,
+            b: b * 0x11,
+        }),
+        [r1, r2, g1, g2, b1, b2] => Ok(RGB8 {
+            r: r1 << 4 | r2,
+            g: g1 << 4 | g2,
+            b: b1 << 4 | b2,
+        }),
+        _ => Err(ColorError::InvalidLength(digits.len())),
+    }
+}
+
+/// Parses CSS functional notation, such as `rgb(242, 242, 242)`, into an [`RGB8`].
+///
+/// Each of the three comma-separated components must be an integer from 0 to 255. Percentages and alpha
+/// channels (`rgba(...)` or a fourth component) are rejected.
+fn parse_rgb_function(s: &str) -> Result<RGB8, ColorError> {
+    let s = s.trim();
+    let lowercase = s.to_ascii_lowercase();
+    if lowercase.starts_with("rgba") {
+        return Err(ColorError::AlphaChannel(s.to_owned()));
+    }
+    let components = lowercase
+        .strip_prefix("rgb")
+        .map(str::trim_start)
+        .and_then(|s| s.strip_prefix('('))
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or_else(|| ColorError::InvalidRgb(s.to_owned()))?
+        .split(',')
+        .map(str::trim)
+        .collect::<Vec<_>>();
+
+    match components[..] {
+        [r, g, b] => Ok(RGB8 {
+            r: parse_rgb_component(r)?,
+            g: parse_rgb_component(g)?,
+            b: parse_rgb_component(b)?,
+        }),
+        [_, _, _, _] => Err(ColorError::AlphaChannel(s.to_owned())),
+        _ => Err(ColorError::InvalidRgb(s.to_owned())),
+    }
+}
+
+fn parse_rgb_component(component: &str) -> Result<u8, ColorError> {
+    component
+        .parse()
+        .map_err(|_| ColorError::InvalidRgbComponent(component.to_owned()))
+}
+
+/// Parses 8 hexadecimal digits with an alpha channel, such as `f2f2f280`, into an [`RGBA8`].
+///
+/// The string may have a single leading `#` and surrounding whitespace. Returns `None` for any other form.
+fn parse_hex_rgba(s: &str) -> Option<RGBA8> {
+    let s = s.trim();
+    let s = s.strip_prefix('#').unwrap_or(s);
+    let mut bytes = [0; 4];
+    hex::decode_to_slice(s, &mut bytes).ok()?;
+    let [r, g, b, a] = bytes;
+    Some(RGBA8 { r, g, b, a })
+}
+
+/// Formats an [`RGB8`] as a 6-character lowercase hexadecimal string without a `#`.
+pub fn color_to_hex(color: RGB8) -> String {
+    hex::encode([color.r, color.g, color.b])
+}
+
+/// The background color of an item, serialized as a 6-character lowercase hexadecimal string without a `#`.
+///
+/// Any form accepted by [`parse_hex_color`] is accepted when parsing or deserializing, as are CSS functional
+/// notation with integer components, such as `rgb(242, 242, 242)`, and CSS color names, such as `white`. See
+/// [`BackgroundColor::from_css_name`].
+///
+/// A background color never has an alpha channel. Metadata keeps the alpha channel of an 8-digit color
+/// (`rrggbbaa`) in [`Metadata::background_color_alpha`](crate::Metadata::background_color_alpha) instead, see
+/// [`BackgroundColor::parse_with_alpha`].
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+#[cfg_attr(
+    feature = "schemars",
+    derive(JsonSchema),
+    schemars(extend(
+        "pattern" = r"^\s*(#?([0-9a-fA-F]{3}|[0-9a-fA-F]{6})|rgb\(\s*\d{1,3}\s*,\s*\d{1,3}\s*,\s*\d{1,3}\s*\)|[a-zA-Z]+)\s*$"
+    ))
+)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BackgroundColor(
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::rgb8::serialize",
+            deserialize_with = "crate::borsh_with::rgb8::deserialize"
+        )
+    )]
+    pub RGB8,
+);
+
+impl BackgroundColor {
+    /// Creates a background color from an [`RGB8`].
+    pub fn from_rgb8(color: RGB8) -> Self {
+        BackgroundColor(color)
+    }
+
+    /// Returns the color as an [`RGB8`].
+    pub fn as_rgb8(self) -> RGB8 {
+        self.0
+    }
+
+    /// Returns the color as an [`RGBA8`] with the given alpha channel, which is fully opaque if `None`.
+    pub fn with_alpha(self, alpha: Option<u8>) -> RGBA8 {
+        self.0.with_alpha(alpha.unwrap_or(u8::MAX))
+    }
+
+    /// Parses any form accepted by [`FromStr`], as well as 8 hexadecimal digits (`rrggbbaa`) whose last two are
+    /// an alpha channel, returning the color and the alpha channel if there is one.
+    pub fn parse_with_alpha(s: &str) -> Result<(Self, Option<u8>), ColorError> {
+        match parse_hex_rgba(s) {
+            Some(color) => Ok((BackgroundColor(color.rgb()), Some(color.a))),
+            None => Ok((s.parse()?, None)),
+        }
+    }
+
+    /// Formats the color as 6 lowercase hexadecimal digits without a `#`, followed by 2 more for the alpha
+    /// channel if there is one. This is the counterpart of [`parse_with_alpha`](BackgroundColor::parse_with_alpha).
+    pub fn to_hex_with_alpha(self, alpha: Option<u8>) -> String {
+        match alpha {
+            Some(alpha) => format!("{}{:02x}", self, alpha),
+            None => self.to_string(),
+        }
+    }
+
+    /// Looks up one of the 148 [CSS named colors](https://www.w3.org/TR/css-color-4/#named-colors), such as
+    /// `white` or `rebeccapurple`.
+    ///
+    /// The name is matched case-insensitively, ignoring surrounding whitespace. The color is still serialized as
+    /// hex.
+    pub fn from_css_name(name: &str) -> Result<Self, ColorError> {
+        let lowercase = name.trim().to_ascii_lowercase();
+        CSS_NAMED_COLORS
+            .binary_search_by(|(candidate, _)| candidate.cmp(&lowercase.as_str()))
+            .map(|index| {
+                let [r, g, b] = CSS_NAMED_COLORS[index].1;
+                BackgroundColor(RGB8 { r, g, b })
+            })
+            .map_err(|_| ColorError::UnknownColorName(name.to_owned()))
+    }
+
+    /// Formats the color as a CSS hexadecimal color, such as `#f2f2f2`.
+    pub fn to_css_hex(self) -> String {
+        format!("#{}", self)
+    }
+}
+
+impl FromStr for BackgroundColor {
+    type Err = ColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim_start()
+            .get(..3)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("rgb"))
+        {
+            return parse_rgb_function(s).map(BackgroundColor);
+        }
+        match parse_hex_color(s) {
+            Ok(color) => Ok(BackgroundColor(color)),
+            Err(_) if s.trim().chars().all(|c| c.is_ascii_alphabetic()) && !s.trim().is_empty() => {
+                BackgroundColor::from_css_name(s)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Formats the color as a 6-character lowercase hexadecimal string without a `#`. See [`color_to_hex`].
+impl fmt::Display for BackgroundColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&color_to_hex(self.0))
+    }
+}
+
+impl From<RGB8> for BackgroundColor {
+    fn from(color: RGB8) -> Self {
+        BackgroundColor(color)
+    }
+}
+
+impl From<BackgroundColor> for RGB8 {
+    fn from(color: BackgroundColor) -> Self {
+        color.0
+    }
+}
+
+impl TryFrom<String> for BackgroundColor {
+    type Error = ColorError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<BackgroundColor> for String {
+    fn from(color: BackgroundColor) -> Self {
+        color.to_string()
+    }
+}
+
+/// Error returned by [`parse_hex_color`] and when parsing a [`BackgroundColor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorError {
+    /// The color did not contain 3 or 6 hexadecimal digits.
+    InvalidLength(usize),
+    /// The color contained a character that is not a hexadecimal digit.
+    InvalidHexDigit(char),
+    /// The name was not one of the CSS named colors.
+    UnknownColorName(String),
+    /// The color looked like `rgb(...)` but was malformed.
+    InvalidRgb(String),
+    /// A component of an `rgb(...)` color was not an integer from 0 to 255.
+    InvalidRgbComponent(String),
+    /// An `rgb(...)` color had an alpha channel, which is only supported as 8 hexadecimal digits.
+    AlphaChannel(String),
+}
+
+impl fmt::Display for ColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorError::InvalidLength(len) => {
+                write!(
+                    f,
+                    "expected a color of 6 or 3 hexadecimal digits with an optional `#`, such as `f2f2f2`, \
+                     `#f2f2f2` or `#fff`, found {} digits",
+                    len
+                )
+            }
+            ColorError::InvalidHexDigit(c) => write!(f, "invalid hexadecimal digit {:?}", c),
+            ColorError::UnknownColorName(name) => write!(f, "unknown CSS color name {:?}", name),
+            ColorError::InvalidRgb(color) => {
+                write!(
+                    f,
+                    "expected a color such as `rgb(242, 242, 242)`, found {:?}",
+                    color
+                )
+            }
+            ColorError::InvalidRgbComponent(component) if component.ends_with('%') => write!(
+                f,
+                "percentages are not supported in `rgb()` colors, found {:?}",
+                component
+            ),
+            ColorError::InvalidRgbComponent(component) => write!(
+                f,
+                "expected `rgb()` components to be integers from 0 to 255, found {:?}",
+                component
+            ),
+            ColorError::AlphaChannel(color) => {
+                write!(
+                    f,
+                    "alpha channels are not supported in `rgb()` colors, use 8 hexadecimal digits instead, \
+                     found {:?}",
+                    color
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ColorError {}
+
+/// The CSS named colors, sorted by name so that they can be binary searched.
+const CSS_NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [0xf0, 0xf8, 0xff]),
+    ("antiquewhite", [0xfa, 0xeb, 0xd7]),
+    ("aqua", [0x00, 0xff, 0xff]),
+    ("aquamarine", [0x7f, 0xff, 0xd4]),
+    ("azure", [0xf0, 0xff, 0xff]),
+    ("beige", [0xf5, 0xf5, 0xdc]),
+    ("bisque", [0xff, 0xe4, 0xc4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xff, 0xeb, 0xcd]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("blueviolet", [0x8a, 0x2b, 0xe2]),
+    ("brown", [0xa5, 0x2a, 0x2a]),
+    ("burlywood", [0xde, 0xb8, 0x87]),
+    ("cadetblue", [0x5f, 0x9e, 0xa0]),
+    ("chartreuse", [0x7f, 0xff, 0x00]),
+    ("chocolate", [0xd2, 0x69, 0x1e]),
+    ("coral", [0xff, 0x7f, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xed]),
+    ("cornsilk", [0xff, 0xf8, 0xdc]),
+    ("crimson", [0xdc, 0x14, 0x3c]),
+    ("cyan", [0x00, 0xff, 0xff]),
+    ("darkblue", [0x00, 0x00, 0x8b]),
+    ("darkcyan", [0x00, 0x8b, 0x8b]),
+    ("darkgoldenrod", [0xb8, 0x86, 0x0b]),
+    ("darkgray", [0xa9, 0xa9, 0xa9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkgrey", [0xa9, 0xa9, 0xa9]),
+    ("darkkhaki", [0xbd, 0xb7, 0x6b]),
+    ("darkmagenta", [0x8b, 0x00, 0x8b]),
+    ("darkolivegreen", [0x55, 0x6b, 0x2f]),
+    ("darkorange", [0xff, 0x8c, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xcc]),
+    ("darkred", [0x8b, 0x00, 0x00]),
+    ("darksalmon", [0xe9, 0x96, 0x7a]),
+    ("darkseagreen", [0x8f, 0xbc, 0x8f]),
+    ("darkslateblue", [0x48, 0x3d, 0x8b]),
+    ("darkslategray", [0x2f, 0x4f, 0x4f]),
+    ("darkslategrey", [0x2f, 0x4f, 0x4f]),
+    ("darkturquoise", [0x00, 0xce, 0xd1]),
+    ("darkviolet", [0x94, 0x00, 0xd3]),
+    ("deeppink", [0xff, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xbf, 0xff]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dimgrey", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1e, 0x90, 0xff]),
+    ("firebrick", [0xb2, 0x22, 0x22]),
+    ("floralwhite", [0xff, 0xfa, 0xf0]),
+    ("forestgreen", [0x22, 0x8b, 0x22]),
+    ("fuchsia", [0xff, 0x00, 0xff]),
+    ("gainsboro", [0xdc, 0xdc, 0xdc]),
+    ("ghostwhite", [0xf8, 0xf8, 0xff]),
+    ("gold", [0xff, 0xd7, 0x00]),
+    ("goldenrod", [0xda, 0xa5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xad, 0xff, 0x2f]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("honeydew", [0xf0, 0xff, 0xf0]),
+    ("hotpink", [0xff, 0x69, 0xb4]),
+    ("indianred", [0xcd, 0x5c, 0x5c]),
+    ("indigo", [0x4b, 0x00, 0x82]),
+    ("ivory", [0xff, 0xff, 0xf0]),
+    ("khaki", [0xf0, 0xe6, 0x8c]),
+    ("lavender", [0xe6, 0xe6, 0xfa]),
+    ("lavenderblush", [0xff, 0xf0, 0xf5]),
+    ("lawngreen", [0x7c, 0xfc, 0x00]),
+    ("lemonchiffon", [0xff, 0xfa, 0xcd]),
+    ("lightblue", [0xad, 0xd8, 0xe6]),
+    ("lightcoral", [0xf0, 0x80, 0x80]),
+    ("lightcyan", [0xe0, 0xff, 0xff]),
+    ("lightgoldenrodyellow", [0xfa, 0xfa, 0xd2]),
+    ("lightgray", [0xd3, 0xd3, 0xd3]),
+    ("lightgreen", [0x90, 0xee, 0x90]),
+    ("lightgrey", [0xd3, 0xd3, 0xd3]),
+    ("lightpink", [0xff, 0xb6, 0xc1]),
+    ("lightsalmon", [0xff, 0xa0, 0x7a]),
+    ("lightseagreen", [0x20, 0xb2, 0xaa]),
+    ("lightskyblue", [0x87, 0xce, 0xfa]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightslategrey", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xb0, 0xc4, 0xde]),
+    ("lightyellow", [0xff, 0xff, 0xe0]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("limegreen", [0x32, 0xcd, 0x32]),
+    ("linen", [0xfa, 0xf0, 0xe6]),
+    ("magenta", [0xff, 0x00, 0xff]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xcd, 0xaa]),
+    ("mediumblue", [0x00, 0x00, 0xcd]),
+    ("mediumorchid", [0xba, 0x55, 0xd3]),
+    ("mediumpurple", [0x93, 0x70, 0xdb]),
+    ("mediumseagreen", [0x3c, 0xb3, 0x71]),
+    ("mediumslateblue", [0x7b, 0x68, 0xee]),
+    ("mediumspringgreen", [0x00, 0xfa, 0x9a]),
+    ("mediumturquoise", [0x48, 0xd1, 0xcc]),
+    ("mediumvioletred", [0xc7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xf5, 0xff, 0xfa]),
+    ("mistyrose", [0xff, 0xe4, 0xe1]),
+    ("moccasin", [0xff, 0xe4, 0xb5]),
+    ("navajowhite", [0xff, 0xde, 0xad]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xfd, 0xf5, 0xe6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6b, 0x8e, 0x23]),
+    ("orange", [0xff, 0xa5, 0x00]),
+    ("orangered", [0xff, 0x45, 0x00]),
+    ("orchid", [0xda, 0x70, 0xd6]),
+    ("palegoldenrod", [0xee, 0xe8, 0xaa]),
+    ("palegreen", [0x98, 0xfb, 0x98]),
+    ("paleturquoise", [0xaf, 0xee, 0xee]),
+    ("palevioletred", [0xdb, 0x70, 0x93]),
+    ("papayawhip", [0xff, 0xef, 0xd5]),
+    ("peachpuff", [0xff, 0xda, 0xb9]),
+    ("peru", [0xcd, 0x85, 0x3f]),
+    ("pink", [0xff, 0xc0, 0xcb]),
+    ("plum", [0xdd, 0xa0, 0xdd]),
+    ("powderblue", [0xb0, 0xe0, 0xe6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("rebeccapurple", [0x66, 0x33, 0x99]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("rosybrown", [0xbc, 0x8f, 0x8f]),
+    ("royalblue", [0x41, 0x69, 0xe1]),
+    ("saddlebrown", [0x8b, 0x45, 0x13]),
+    ("salmon", [0xfa, 0x80, 0x72]),
+    ("sandybrown", [0xf4, 0xa4, 0x60]),
+    ("seagreen", [0x2e, 0x8b, 0x57]),
+    ("seashell", [0xff, 0xf5, 0xee]),
+    ("sienna", [0xa0, 0x52, 0x2d]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("skyblue", [0x87, 0xce, 0xeb]),
+    ("slateblue", [0x6a, 0x5a, 0xcd]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("slategrey", [0x70, 0x80, 0x90]),
+    ("snow", [0xff, 0xfa, 0xfa]),
+    ("springgreen", [0x00, 0xff, 0x7f]),
+    ("steelblue", [0x46, 0x82, 0xb4]),
+    ("tan", [0xd2, 0xb4, 0x8c]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xd8, 0xbf, 0xd8]),
+    ("tomato", [0xff, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xe0, 0xd0]),
+    ("violet", [0xee, 0x82, 0xee]),
+    ("wheat", [0xf5, 0xde, 0xb3]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("whitesmoke", [0xf5, 0xf5, 0xf5]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("yellowgreen", [0x9a, 0xcd, 0x32]),
+];
+
+#[cfg(test)]
+mod tests {
+    use rgb::{RGB8, RGBA8};
+
+    use crate::{color_to_hex, parse_hex_color, BackgroundColor, ColorError};
+
+    #[test]
+    fn parse() {
+        let white = RGB8 {
+            r: 255,
+            g: 255,
+            b: 255,
+        };
+        assert_eq!(parse_hex_color("ffffff"), Ok(white));
+        assert_eq!(parse_hex_color("#FFFFFF"), Ok(white));
+        assert_eq!(parse_hex_color("#fff"), Ok(white));
+        assert_eq!(
+            parse_hex_color("1e90ff"),
+            Ok(RGB8 {
+                r: 0x1e,
+                g: 0x90,
+                b: 0xff
+            })
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(parse_hex_color("f2f2"), Err(ColorError::InvalidLength(4)));
+        assert_eq!(parse_hex_color(""), Err(ColorError::InvalidLength(0)));
+        assert_eq!(
+            parse_hex_color("#f2f2fg"),
+            Err(ColorError::InvalidHexDigit('g'))
+        );
+    }
+
+    #[test]
+    fn hash_prefix() {
+        let grey = RGB8::new(0xf2, 0xf2, 0xf2);
+        assert_eq!(parse_hex_color("#f2f2f2"), Ok(grey));
+        assert_eq!(parse_hex_color("f2f2f2"), Ok(grey));
+        assert_eq!(parse_hex_color(" #f2f2f2\n"), Ok(grey));
+        assert_eq!(parse_hex_color("#f2"), Err(ColorError::InvalidLength(2)));
+        assert!(ColorError::InvalidLength(2)
+            .to_string()
+            .contains("optional `#`"));
+        assert_eq!(
+            parse_hex_color("# f2f2f2"),
+            Err(ColorError::InvalidHexDigit(' '))
+        );
+    }
+
+    #[test]
+    fn to_hex() {
+        let color = RGB8 {
+            r: 0x1e,
+            g: 0x90,
+            b: 0xff,
+        };
+        assert_eq!(color_to_hex(color), "1e90ff");
+        assert_eq!(parse_hex_color(&color_to_hex(color)), Ok(color));
+    }
+
+    #[test]
+    fn background_color() {
+        let color = "#F2F2F2".parse::<BackgroundColor>().unwrap();
+        assert_eq!(
+            color,
+            BackgroundColor::from_rgb8(RGB8::new(0xf2, 0xf2, 0xf2))
+        );
+        assert_eq!(color.as_rgb8(), RGB8::new(0xf2, 0xf2, 0xf2));
+        assert_eq!(color.to_string(), "f2f2f2");
+        assert_eq!(color.to_css_hex(), "#f2f2f2");
+        assert_eq!(
+            "f2".parse::<BackgroundColor>(),
+            Err(ColorError::InvalidLength(2))
+        );
+        assert_eq!(
+            BackgroundColor::from(RGB8::new(0, 0, 0)).to_string(),
+            "000000"
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for (s, display) in [
+            ("f2f2f2", "f2f2f2"),
+            ("#F2F2F2", "f2f2f2"),
+            ("#0af", "00aaff"),
+            (" FFF ", "ffffff"),
+            ("000000", "000000"),
+        ] {
+            let color = s.parse::<BackgroundColor>().unwrap();
+            assert_eq!(color.to_string(), display);
+            assert_eq!(display.parse(), Ok(color));
+        }
+
+        for color in [
+            RGB8::new(0, 0, 0),
+            RGB8::new(0x01, 0x23, 0x45),
+            RGB8::new(0xfe, 0xdc, 0xba),
+            RGB8::new(0xff, 0xff, 0xff),
+        ] {
+            let color = BackgroundColor::from_rgb8(color);
+            assert_eq!(color.to_string().parse(), Ok(color));
+            assert_eq!(color.to_css_hex().parse(), Ok(color));
+        }
+    }
+
+    #[test]
+    fn alpha_channel() {
+        let (color, alpha) = BackgroundColor::parse_with_alpha("#F2F2F280").unwrap();
+        assert_eq!(color, BackgroundColor(RGB8::new(0xf2, 0xf2, 0xf2)));
+        assert_eq!(alpha, Some(0x80));
+        assert_eq!(color.with_alpha(alpha), RGBA8::new(0xf2, 0xf2, 0xf2, 0x80));
+        assert_eq!(color.to_hex_with_alpha(alpha), "f2f2f280");
+        assert_eq!(
+            BackgroundColor::parse_with_alpha(&color.to_hex_with_alpha(alpha)),
+            Ok((color, alpha))
+        );
+
+        assert_eq!(
+            BackgroundColor::parse_with_alpha("f2f2f2ff"),
+            Ok((color, Some(0xff)))
+        );
+        assert_eq!(
+            BackgroundColor::parse_with_alpha("white"),
+            Ok((BackgroundColor(RGB8::new(0xff, 0xff, 0xff)), None))
+        );
+        assert_eq!(color.with_alpha(None), RGBA8::new(0xf2, 0xf2, 0xf2, 0xff));
+        assert_eq!(color.to_hex_with_alpha(None), "f2f2f2");
+
+        assert_eq!(
+            "f2f2f280".parse::<BackgroundColor>(),
+            Err(ColorError::InvalidLength(8))
+        );
+        assert_eq!(
+            BackgroundColor::parse_with_alpha("f2f2f2g0"),
+            Err(ColorError::InvalidHexDigit('g'))
+        );
+    }
+
+    #[test]
+    fn css_names() {
+        assert_eq!(
+            BackgroundColor::from_css_name("white"),
+            Ok(BackgroundColor::from_rgb8(RGB8::new(0xff, 0xff, 0xff)))
+        );
+        let color = BackgroundColor::from_css_name(" RebeccaPurple ").unwrap();
+        assert_eq!(color.as_rgb8(), RGB8::new(0x66, 0x33, 0x99));
+        assert_eq!(color.to_string(), "663399");
+
+        let error = BackgroundColor::from_css_name("blurple").unwrap_err();
+        assert_eq!(error, ColorError::UnknownColorName("blurple".into()));
+        assert!(error.to_string().contains("\"blurple\""));
+        assert_eq!("White".parse(), BackgroundColor::from_css_name("white"));
+        assert_eq!(
+            "blurple".parse::<BackgroundColor>(),
+            Err(ColorError::UnknownColorName("blurple".into()))
+        );
+        assert_eq!(
+            "#blurple".parse::<BackgroundColor>(),
+            Err(ColorError::InvalidHexDigit('l'))
+        );
+        assert_eq!(
+            "bad".parse(),
+            Ok(BackgroundColor::from_rgb8(RGB8::new(0xbb, 0xaa, 0xdd)))
+        );
+    }
+
+    #[test]
+    fn rgb_function() {
+        for s in [
+            "rgb(242, 242, 242)",
+            " RGB( 242,242 , 242 ) ",
+            "rgb (242,242,242)",
+        ] {
+            let color = s.parse::<BackgroundColor>().unwrap();
+            assert_eq!(color.as_rgb8(), RGB8::new(0xf2, 0xf2, 0xf2));
+            assert_eq!(color.to_string(), "f2f2f2");
+        }
+        assert_eq!(
+            "rgb(0, 128, 255)".parse(),
+            Ok(BackgroundColor::from_rgb8(RGB8::new(0x00, 0x80, 0xff)))
+        );
+    }
+
+    #[test]
+    fn invalid_rgb_function() {
+        assert_eq!(
+            "rgb(256, 0, 0)".parse::<BackgroundColor>(),
+            Err(ColorError::InvalidRgbComponent("256".into()))
+        );
+        assert_eq!(
+            "rgb(0, -1, 0)".parse::<BackgroundColor>(),
+            Err(ColorError::InvalidRgbComponent("-1".into()))
+        );
+        let error = "rgb(100%, 0%, 0%)".parse::<BackgroundColor>().unwrap_err();
+        assert_eq!(error, ColorError::InvalidRgbComponent("100%".into()));
+        assert!(error.to_string().contains("percentages are not supported"));
+
+        let error = "rgba(242, 242, 242, 0.5)"
+            .parse::<BackgroundColor>()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            ColorError::AlphaChannel("rgba(242, 242, 242, 0.5)".into())
+        );
+        assert!(error
+            .to_string()
+            .contains("alpha channels are not supported"));
+        assert_eq!(
+            "rgb(242, 242, 242, 0.5)".parse::<BackgroundColor>(),
+            Err(ColorError::AlphaChannel("rgb(242, 242, 242, 0.5)".into()))
+        );
+
+        for s in ["rgb(242, 242)", "rgb 242, 242, 242", "rgb(242, 242, 242"] {
+            assert_eq!(
+                s.parse::<BackgroundColor>(),
+                Err(ColorError::InvalidRgb(s.into()))
+            );
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use rgb::RGB8;
+    use serde::{Deserialize, Serialize};
+
+    use crate::BackgroundColor;
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Target {
+        color: Option<BackgroundColor>,
+    }
+
+    #[test]
+    fn from_json() {
+        let s = r#"{ "color": "f2f2f2" }"#;
+        let target: Target = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            target.color.map(BackgroundColor::as_rgb8),
+            Some(RGB8 {
+                r: 242,
+                g: 242,
+                b: 242
+            })
+        );
+    }
+
+    #[test]
+    fn to_json() {
+        let target = Target {
+            color: Some(
+                RGB8 {
+                    r: 242,
+                    g: 242,
+                    b: 242,
+                }
+                .into(),
+            ),
+        };
+        let s = serde_json::to_string(&target).unwrap();
+        assert_eq!(s, r#"{"color":"f2f2f2"}"#)
+    }
+
+    #[test]
+    fn from_notcolor_json() {
+        let s = r#"{ "color": "f2f2f2f2" }"#;
+        let target = serde_json::from_str::<Target>(s);
+        assert!(target.is_err());
+    }
+
+    #[test]
+    fn from_hash_prefixed_json() {
+        let s = r##"{ "color": "#ffffff" }"##;
+        let target: Target = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            target.color.map(BackgroundColor::as_rgb8),
+            Some(RGB8 {
+                r: 255,
+                g: 255,
+                b: 255
+            })
+        );
+    }
+
+    #[test]
+    fn from_invalid_hash_prefixed_json() {
+        let s = r##"{ "color": "#f2" }"##;
+        let error = serde_json::from_str::<Target>(s).unwrap_err();
+        assert!(error.to_string().contains("`#f2f2f2`"));
+
+        let s = r##"{ "color": " #f2f2f2 " }"##;
+        let target: Target = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            target.color.map(BackgroundColor::as_rgb8),
+            Some(RGB8::new(0xf2, 0xf2, 0xf2))
+        );
+    }
+
+    #[test]
+    fn from_shorthand_json() {
+        let s = r#"{ "color": "fff" }"#;
+        let target: Target = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            target.color.map(BackgroundColor::as_rgb8),
+            Some(RGB8 {
+                r: 255,
+                g: 255,
+                b: 255
+            })
+        );
+
+        let s = r#"{ "color": "abc" }"#;
+        let target: Target = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            target.color.map(BackgroundColor::as_rgb8),
+            Some(RGB8 {
+                r: 0xaa,
+                g: 0xbb,
+                b: 0xcc
+            })
+        );
+    }
+
+    #[test]
+    fn shorthand_round_trip() {
+        let s = r##"{ "color": "#0af" }"##;
+        let target: Target = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            target.color.map(BackgroundColor::as_rgb8),
+            Some(RGB8::new(0x00, 0xaa, 0xff))
+        );
+        assert_eq!(
+            serde_json::to_string(&target).unwrap(),
+            r#"{"color":"00aaff"}"#
+        );
+
+        for color in ["f2f2", "f2f2f", "#f2f2f2f"] {
+            let s = format!(r#"{{ "color": "{}" }}"#, color);
+            let error = serde_json::from_str::<Target>(&s).unwrap_err();
+            assert!(error.to_string().contains("6 or 3 hexadecimal digits"));
+        }
+    }
+
+    #[test]
+    fn from_rgb_function_json() {
+        let s = r#"{ "color": "rgb(242, 242, 242)" }"#;
+        let target: Target = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            target.color,
+            Some(BackgroundColor::from_rgb8(RGB8::new(0xf2, 0xf2, 0xf2)))
+        );
+        assert_eq!(
+            serde_json::to_string(&target).unwrap(),
+            r#"{"color":"f2f2f2"}"#
+        );
+
+        let s = r#"{ "color": "rgba(242, 242, 242, 1)" }"#;
+        let error = serde_json::from_str::<Target>(s).unwrap_err();
+        assert!(error.to_string().contains("alpha channels"));
+    }
+
+    #[test]
+    fn from_css_name_json() {
+        let s = r#"{ "color": "white" }"#;
+        let target: Target = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            target.color,
+            Some(BackgroundColor::from_rgb8(RGB8::new(0xff, 0xff, 0xff)))
+        );
+        assert_eq!(
+            serde_json::to_string(&target).unwrap(),
+            r#"{"color":"ffffff"}"#
+        );
+
+        let s = r#"{ "color": "blurple" }"#;
+        let error = serde_json::from_str::<Target>(s).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unknown CSS color name \"blurple\""));
+    }
+
+    #[test]
+    fn from_null_json() {
+        let s = r#"{ "color": null }"#;
+        let target = serde_json::from_str::<Target>(s).unwrap();
+        assert!(target.color.is_none());
+    }
+}
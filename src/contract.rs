@@ -0,0 +1,192 @@
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::ValidationError;
+
+/// The maximum royalty, in basis points.
+pub(crate) const MAX_BASIS_POINTS: u32 = 10_000;
+
+/// Collection-level metadata, as returned by a contract's `contractURI()`.
+///
+/// See [OpenSea's contract-level metadata](https://docs.opensea.io/docs/contract-level-metadata).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContractMetadata {
+    /// Name of the collection.
+    pub name: String,
+    /// Human-readable description of the collection.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+    /// URL to image of the collection.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub image: Option<Url>,
+    /// URL to banner image of the collection.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub banner_image: Option<Url>,
+    /// URL to featured image of the collection.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub featured_image: Option<Url>,
+    /// External URL to another site.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub external_link: Option<Url>,
+    /// Addresses of accounts allowed to manage the collection.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub collaborators: Vec<String>,
+    /// Seller fee in basis points, where 100 is a 1% fee.
+    ///
+    /// This field is deprecated by OpenSea in favour of on-chain royalties.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub seller_fee_basis_points: Option<u32>,
+    /// Address that seller fees are paid to.
+    ///
+    /// This field is deprecated by OpenSea in favour of on-chain royalties.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub fee_recipient: Option<String>,
+}
+
+impl ContractMetadata {
+    /// Checks that the seller fee is at most 10000 basis points, and that a fee recipient is set
+    /// whenever a fee is.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        if let Some(basis_points) = self.seller_fee_basis_points {
+            if basis_points > MAX_BASIS_POINTS {
+                errors.push(ValidationError::FeeTooHigh(basis_points));
+            }
+            if self.fee_recipient.is_none() {
+                errors.push(ValidationError::MissingFeeRecipient);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ContractMetadata, ValidationError};
+
+    fn contract_metadata() -> ContractMetadata {
+        ContractMetadata {
+            name: "OpenSea Creatures".into(),
+            description: None,
+            image: None,
+            banner_image: None,
+            featured_image: None,
+            external_link: None,
+            collaborators: Vec::new(),
+            seller_fee_basis_points: None,
+            fee_recipient: None,
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn opensea_example() {
+        let s = r#"
+        {
+            "name": "OpenSea Creatures",
+            "description": "OpenSea Creatures are adorable aquatic beings primarily for demonstrating what can be done using the OpenSea platform. Adopt one today to try out all the OpenSea buying, selling, and bidding feature set.",
+            "image": "https://external-link-url.com/image.png",
+            "banner_image": "https://external-link-url.com/banner-image.png",
+            "featured_image": "https://external-link-url.com/featured-image.png",
+            "external_link": "https://external-link-url.com/",
+            "collaborators": ["0x0000000000000000000000000000000000000000"],
+            "seller_fee_basis_points": 100,
+            "fee_recipient": "0xA97F337c39cccE66adfeCB2BF99C1DdC54C2D721"
+        }
+        "#;
+        let metadata = serde_json::from_str::<ContractMetadata>(s).unwrap();
+        assert_eq!(metadata.seller_fee_basis_points, Some(100));
+        assert_eq!(metadata.validate(), Ok(()));
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::from_str::<serde_json::Value>(s).unwrap()
+        );
+
+        let minimal = serde_json::to_string(&contract_metadata()).unwrap();
+        assert_eq!(minimal, r#"{"name":"OpenSea Creatures"}"#);
+    }
+
+    #[test]
+    fn invalid_fee() {
+        let metadata = ContractMetadata {
+            seller_fee_basis_points: Some(10_001),
+            ..contract_metadata()
+        };
+        assert_eq!(
+            metadata.validate(),
+            Err(vec![
+                ValidationError::FeeTooHigh(10_001),
+                ValidationError::MissingFeeRecipient,
+            ])
+        );
+
+        let metadata = ContractMetadata {
+            seller_fee_basis_points: Some(10_000),
+            fee_recipient: Some("0xA97F337c39cccE66adfeCB2BF99C1DdC54C2D721".into()),
+            ..contract_metadata()
+        };
+        assert_eq!(metadata.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,89 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::Metadata;
+
+/// The decoded contents of a [`data:` URI](https://www.rfc-editor.org/rfc/rfc2397), as used by fully on-chain
+/// tokens to embed an image directly in the metadata.
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DataUri {
+    /// MIME type of the data, such as `"image/png"`, without any parameters.
+    ///
+    /// This is `"text/plain"` if the URI does not specify a type.
+    pub mime: String,
+    /// The decoded data.
+    pub bytes: Vec<u8>,
+}
+
+impl DataUri {
+    /// Decodes a `data:` URI, in either its base64 or percent-encoded form.
+    ///
+    /// Returns `None` if `uri` is not a `data:` URI or its data cannot be decoded.
+    pub fn parse(uri: &str) -> Option<DataUri> {
+        let (header, data) = uri.strip_prefix("data:")?.split_once(',')?;
+        let mut params = header.split(';');
+        let mime = match params.next().map(str::trim) {
+            Some(mime) if !mime.is_empty() => mime.to_ascii_lowercase(),
+            _ => "text/plain".to_owned(),
+        };
+        let data = percent_encoding::percent_decode_str(data).collect::<Vec<_>>();
+
+        let bytes = if params.any(|param| param.trim().eq_ignore_ascii_case("base64")) {
+            STANDARD.decode(data).ok()?
+        } else {
+            data
+        };
+        Some(DataUri { mime, bytes })
+    }
+}
+
+impl Metadata {
+    /// Decodes the [`image`](Metadata::image) if it is a `data:` URI. See [`DataUri::parse`].
+    pub fn image_data_uri(&self) -> Option<DataUri> {
+        DataUri::parse(self.image.as_ref()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DataUri, Metadata, RawUrl};
+
+    /// A 1x1 transparent PNG.
+    const PNG: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mNkYAAAAAYAAjCB0C8AAAAASUVORK5CYII=";
+
+    #[test]
+    fn png() {
+        let metadata = Metadata {
+            image: Some(RawUrl(format!("data:image/png;base64,{}", PNG))),
+            ..Default::default()
+        };
+        let data_uri = metadata.image_data_uri().unwrap();
+        assert_eq!(data_uri.mime, "image/png");
+        assert_eq!(data_uri.bytes.len(), 68);
+        assert_eq!(&data_uri.bytes[..8], b"\x89PNG\r\n\x1a\n");
+
+        assert_eq!(Metadata::default().image_data_uri(), None);
+    }
+
+    #[test]
+    fn forms() {
+        assert_eq!(
+            DataUri::parse("data:image/svg+xml;utf8,%3Csvg%3E%3C/svg%3E"),
+            Some(DataUri {
+                mime: "image/svg+xml".into(),
+                bytes: b"<svg></svg>".to_vec(),
+            })
+        );
+        assert_eq!(
+            DataUri::parse("data:,Hello"),
+            Some(DataUri {
+                mime: "text/plain".into(),
+                bytes: b"Hello".to_vec(),
+            })
+        );
+        assert_eq!(DataUri::parse("data:image/png;base64,!!!"), None);
+        assert_eq!(DataUri::parse("https://example.com/0.png"), None);
+    }
+}
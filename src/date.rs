@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+
+use crate::{AttributeEntry, AttributeValue, DisplayType};
+
+impl AttributeEntry {
+    /// Interprets the value as a Unix timestamp in seconds, if the attribute is displayed as a
+    /// [`Date`](DisplayType::Date).
+    ///
+    /// Returns `None` for any other display type, for non-numerical values, and for timestamps that are out of
+    /// range. Fractional seconds are kept.
+    pub fn as_datetime(&self) -> Option<DateTime<Utc>> {
+        if self.display_type != Some(DisplayType::Date) {
+            return None;
+        }
+        match self.value {
+            AttributeValue::Integer(seconds) => DateTime::from_timestamp(seconds, 0),
+            AttributeValue::Float(seconds) if seconds.is_finite() => {
+                let whole = seconds.floor();
+                let nanoseconds = ((seconds.0 - whole) * 1e9) as u32;
+                DateTime::from_timestamp(whole as i64, nanoseconds)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Datelike, Timelike};
+
+    use crate::{AttributeEntry, AttributeValue, DisplayType};
+
+    #[test]
+    fn timestamp() {
+        let datetime = AttributeEntry::date("birthday", 1_546_360_800)
+            .as_datetime()
+            .unwrap();
+        assert_eq!(
+            (datetime.year(), datetime.month(), datetime.day()),
+            (2019, 1, 1)
+        );
+        assert_eq!((datetime.hour(), datetime.minute()), (16, 40));
+
+        let attribute = AttributeEntry {
+            display_type: Some(DisplayType::Date),
+            ..AttributeEntry::float("birthday", 1_546_360_800.5)
+        };
+        assert_eq!(
+            attribute.as_datetime(),
+            DateTime::from_timestamp(1_546_360_800, 500_000_000)
+        );
+    }
+
+    #[test]
+    fn not_a_date() {
+        assert_eq!(
+            AttributeEntry::number("birthday", 1_546_360_800).as_datetime(),
+            None
+        );
+        let attribute = AttributeEntry {
+            value: AttributeValue::from("2019-01-01"),
+            ..AttributeEntry::date("birthday", 0)
+        };
+        assert_eq!(attribute.as_datetime(), None);
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{BuilderError, Metadata, MetadataBuilder};
+
+/// Metadata for an ERC-721 token, following the
+/// [ERC-721 Metadata JSON Schema](https://eips.ethereum.org/EIPS/eip-721#specification).
+///
+/// Every field is optional in the schema.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Eip721Metadata {
+    /// Identifies the asset to which this token represents.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub name: Option<String>,
+    /// Describes the asset to which this token represents.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+    /// A URI pointing to a resource with mime type `image/*` representing the asset to which this token
+    /// represents.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub image: Option<Url>,
+}
+
+/// Converts OpenSea metadata into ERC-721 metadata.
+///
+/// Only the name, description and image are kept. A relative image is dropped, so call
+/// [`Metadata::resolve_urls`] first.
+impl From<Metadata> for Eip721Metadata {
+    fn from(metadata: Metadata) -> Self {
+        Eip721Metadata {
+            name: Some(metadata.name),
+            description: Some(metadata.description),
+            image: metadata.image.and_then(|image| image.parse().ok()),
+        }
+    }
+}
+
+/// Converts ERC-721 metadata into OpenSea metadata, failing if a field required by [`Metadata`] is missing.
+impl TryFrom<Eip721Metadata> for Metadata {
+    type Error = BuilderError;
+
+    fn try_from(metadata: Eip721Metadata) -> Result<Self, Self::Error> {
+        let mut builder = MetadataBuilder::new();
+        if let Some(name) = metadata.name {
+            builder = builder.name(name);
+        }
+        if let Some(description) = metadata.description {
+            builder = builder.description(description);
+        }
+        if let Some(image) = metadata.image {
+            builder = builder.image(image);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BuilderError, Eip721Metadata, Metadata};
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn planetpass() {
+        let metadata = serde_json::from_str::<Metadata>(crate::tests::PLANETPASS_ITEM).unwrap();
+        let eip721 = Eip721Metadata::from(metadata.clone());
+        assert_eq!(eip721.name.as_deref(), Some(crate::fixtures::NAME));
+        assert_eq!(
+            eip721.image.as_ref().map(|image| image.as_str()),
+            Some(crate::fixtures::IMAGE)
+        );
+        assert_eq!(
+            serde_json::to_value(&eip721).unwrap(),
+            crate::fixtures::metadata_json()
+        );
+
+        let round_trip = Metadata::try_from(eip721).unwrap();
+        assert_eq!(round_trip.name, metadata.name);
+        assert_eq!(round_trip.description, metadata.description);
+        assert_eq!(round_trip.image, metadata.image);
+        assert!(round_trip.attributes.is_empty());
+    }
+
+    #[test]
+    fn missing_required_field() {
+        let metadata = Eip721Metadata {
+            name: Some(crate::fixtures::NAME.into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            Metadata::try_from(metadata),
+            Err(BuilderError::MissingField("image"))
+        );
+    }
+}
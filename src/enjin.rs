@@ -0,0 +1,274 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{AttributeEntry, AttributeValue, Metadata};
+
+/// Traits in [Enjin's metadata format](https://docs.enjin.io/docs/metadata-standard), where the `properties`
+/// object is keyed by trait name.
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[serde(transparent)]
+pub struct EnjinProperties(pub BTreeMap<String, EnjinProperty>);
+
+/// A single entry of [`EnjinProperties`].
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum EnjinProperty {
+    /// An object describing the value and how to display it.
+    Rich(RichProperty),
+    /// A bare value.
+    Simple(
+        #[cfg_attr(
+            feature = "borsh",
+            borsh(
+                serialize_with = "crate::borsh_with::json::serialize",
+                deserialize_with = "crate::borsh_with::json::deserialize"
+            )
+        )]
+        Value,
+    ),
+}
+
+/// An [`EnjinProperty`] with display information.
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RichProperty {
+    /// Human-readable name of the property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Value of the property, which is `null` if only [`display_value`](RichProperty::display_value) is given.
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::json::serialize",
+            deserialize_with = "crate::borsh_with::json::deserialize"
+        )
+    )]
+    pub value: Value,
+    /// How the value should be displayed, such as `"123 Example Value"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_value: Option<String>,
+    /// CSS class used to display the property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    /// CSS styles used to display the property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::json::serialize",
+            deserialize_with = "crate::borsh_with::json::deserialize"
+        )
+    )]
+    pub css: Option<Map<String, Value>>,
+}
+
+impl EnjinProperty {
+    /// Returns the value of the property.
+    pub fn value(&self) -> &Value {
+        match self {
+            EnjinProperty::Rich(property) => &property.value,
+            EnjinProperty::Simple(value) => value,
+        }
+    }
+
+    /// Returns how the value should be displayed, if given.
+    pub fn display_value(&self) -> Option<&str> {
+        match self {
+            EnjinProperty::Rich(property) => property.display_value.as_deref(),
+            EnjinProperty::Simple(_) => None,
+        }
+    }
+}
+
+impl EnjinProperties {
+    /// Creates properties from the attributes with a trait type, keyed by that trait type.
+    ///
+    /// Generic attributes are skipped, and later attributes override earlier ones with the same trait type.
+    pub fn from_attributes(attributes: &[AttributeEntry]) -> Self {
+        EnjinProperties(
+            attributes
+                .iter()
+                .filter_map(|attribute| {
+                    let value = match &attribute.value {
+                        AttributeValue::String(value) => Value::from(value.as_str()),
+                        AttributeValue::Integer(value) => Value::from(*value),
+                        AttributeValue::Float(value) => Value::from(value.0),
+                        AttributeValue::Bool(value) => Value::from(*value),
+                    };
+                    Some((
+                        attribute.trait_type()?.to_owned(),
+                        EnjinProperty::Simple(value),
+                    ))
+                })
+                .collect(),
+        )
+    }
+
+    /// Converts the properties into attributes, using the key as the trait type.
+    ///
+    /// A property with only a display value becomes a textual attribute of it. Other properties whose value is
+    /// an array, an object or `null` have no attribute equivalent and are skipped.
+    pub fn to_attributes(&self) -> Vec<AttributeEntry> {
+        self.0
+            .iter()
+            .filter_map(|(trait_type, property)| {
+                let value = match property.value() {
+                    Value::String(value) => AttributeValue::from(value.as_str()),
+                    Value::Bool(value) => AttributeValue::from(*value),
+                    Value::Number(value) => match value.as_i64() {
+                        Some(value) => AttributeValue::from(value),
+                        None => AttributeValue::from(value.as_f64()?),
+                    },
+                    Value::Null => AttributeValue::from(property.display_value()?),
+                    _ => return None,
+                };
+                Some(AttributeEntry::new(trait_type.as_str(), value))
+            })
+            .collect()
+    }
+}
+
+impl Metadata {
+    /// Parses metadata in Enjin's format, appending an attribute for each entry of `properties` whose trait
+    /// type is not already among the attributes, so that serializing and parsing again does not duplicate them.
+    ///
+    /// The original `properties` object is kept in [`Metadata::properties`], so details without an
+    /// attribute equivalent, such as `display_value`, are preserved when serializing again.
+    pub fn from_str_enjin(s: &str) -> Result<Metadata, serde_json::Error> {
+        let mut metadata = serde_json::from_str::<Metadata>(s)?;
+        if let Some(properties) = metadata.enjin_properties()? {
+            let attributes = properties
+                .to_attributes()
+                .into_iter()
+                .filter(|attribute| {
+                    !metadata
+                        .attributes
+                        .iter()
+                        .any(|existing| existing.trait_type == attribute.trait_type)
+                })
+                .collect::<Vec<_>>();
+            metadata.attributes.extend(attributes);
+        }
+        Ok(metadata)
+    }
+
+    /// Interprets [`Metadata::properties`] as [`EnjinProperties`].
+    pub fn enjin_properties(&self) -> Result<Option<EnjinProperties>, serde_json::Error> {
+        self.properties
+            .as_ref()
+            .map(|properties| serde_json::from_value(Value::Object(properties.0.clone())))
+            .transpose()
+    }
+
+    /// Exports the attributes as [`EnjinProperties`], merged over any existing [`Metadata::properties`].
+    ///
+    /// Existing entries are kept as-is, so their display information survives the export.
+    pub fn to_enjin_properties(&self) -> Result<EnjinProperties, serde_json::Error> {
+        let mut properties = self.enjin_properties()?.unwrap_or_default();
+        for (trait_type, property) in EnjinProperties::from_attributes(&self.attributes).0 {
+            properties.0.entry(trait_type).or_insert(property);
+        }
+        Ok(properties)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        fixtures::EIP_1155_EXAMPLE, AttributeEntry, EnjinProperties, EnjinProperty, Metadata,
+    };
+
+    #[test]
+    fn eip_1155_example() {
+        let metadata = Metadata::from_str_enjin(EIP_1155_EXAMPLE).unwrap();
+        assert_eq!(
+            metadata.attributes,
+            [
+                AttributeEntry::string("rich_property", "123"),
+                AttributeEntry::string("simple_property", "example value"),
+            ]
+        );
+
+        let properties = metadata.enjin_properties().unwrap().unwrap();
+        match &properties.0["rich_property"] {
+            EnjinProperty::Rich(property) => {
+                assert_eq!(property.display_value.as_deref(), Some("123 Example Value"))
+            }
+            EnjinProperty::Simple(_) => panic!("expected a rich property"),
+        }
+        assert_eq!(properties.0["array_property"].value(), &json!([1, 2, 3, 4]));
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(
+            value["properties"],
+            serde_json::from_str::<serde_json::Value>(EIP_1155_EXAMPLE).unwrap()["properties"]
+        );
+        assert_eq!(metadata.to_enjin_properties().unwrap(), properties);
+    }
+
+    #[test]
+    fn rich_properties() {
+        let value = crate::fixtures::metadata_json_with(json!({
+            "properties": {
+                "level": { "name": "Level", "value": 3, "display_value": "Level 3" },
+                "speed": { "name": "Speed", "value": 1.5, "class": "emphasis" },
+                "rank": { "name": "Rank", "display_value": "Captain" }
+            }
+        }));
+        let metadata = Metadata::from_str_enjin(&value.to_string()).unwrap();
+        assert_eq!(
+            metadata.attributes,
+            [
+                AttributeEntry::new("level", 3),
+                AttributeEntry::string("rank", "Captain"),
+                AttributeEntry::new("speed", 1.5),
+            ]
+        );
+
+        let properties = metadata.enjin_properties().unwrap().unwrap();
+        assert_eq!(properties.0["level"].display_value(), Some("Level 3"));
+        assert_eq!(properties.0["rank"].value(), &serde_json::Value::Null);
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap()["properties"],
+            value["properties"]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let metadata = Metadata::from_str_enjin(EIP_1155_EXAMPLE).unwrap();
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert_eq!(Metadata::from_str_enjin(&json).unwrap(), metadata);
+
+        let json = json.replace(r#""value":"example value""#, r#""value":"edited""#);
+        assert_eq!(
+            Metadata::from_str_enjin(&json).unwrap().attributes[1],
+            AttributeEntry::string("simple_property", "edited")
+        );
+    }
+
+    #[test]
+    fn export() {
+        let attributes = [
+            AttributeEntry::string("Core", "Vortex"),
+            AttributeEntry::number("Level", 5),
+            AttributeEntry::bool("Shiny", true),
+            AttributeEntry::generic("Rare"),
+        ];
+        let properties = EnjinProperties::from_attributes(&attributes);
+        assert_eq!(
+            serde_json::to_value(&properties).unwrap(),
+            json!({ "Core": "Vortex", "Level": 5, "Shiny": true })
+        );
+        assert_eq!(properties.to_attributes().len(), 3);
+    }
+}
@@ -0,0 +1,278 @@
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    AttributeEntry, BuilderError, Metadata, MetadataBuilder, Properties, RawUrl, ValidationError,
+};
+
+/// Metadata for an ERC-1155 token, following the
+/// [ERC-1155 Metadata URI JSON Schema](https://eips.ethereum.org/EIPS/eip-1155#erc-1155-metadata-uri-json-schema).
+///
+/// Every field is optional in the schema. OpenSea-style [`attributes`](Erc1155Metadata::attributes) are also
+/// accepted, since many documents carry both.
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Erc1155Metadata {
+    /// Name of the asset this token represents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Number of decimal places the token amount should display.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+    /// Description of the asset this token represents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// URL to image of the asset.
+    ///
+    /// Kept as written, since it may contain the `{id}` placeholder, which parsing as a [`Url`] would
+    /// percent-encode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<RawUrl>,
+    /// Arbitrary properties of the asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Properties>,
+    /// OpenSea-style attributes of the asset.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeEntry>,
+    /// Where to find translated versions of this metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localization: Option<Localization>,
+}
+
+/// Localization of ERC-1155 metadata.
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Localization {
+    /// URI template of the localized metadata, where `{locale}` is replaced with the locale.
+    ///
+    /// Kept as a string, since parsing it as a [`Url`] would percent-encode the placeholder.
+    pub uri: String,
+    /// Locale of the data in the base metadata.
+    pub default: String,
+    /// Locales for which localized metadata is available.
+    pub locales: Vec<String>,
+}
+
+impl Localization {
+    /// The placeholder in [`uri`](Localization::uri) that is replaced with the locale.
+    pub const PLACEHOLDER: &'static str = "{locale}";
+
+    /// Checks that the URI contains the `{locale}` placeholder and that the default locale is listed.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        if !self.uri.contains(Self::PLACEHOLDER) {
+            errors.push(ValidationError::MissingLocalePlaceholder);
+        }
+        if !self.locales.contains(&self.default) {
+            errors.push(ValidationError::UnlistedDefaultLocale(self.default.clone()));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the URI of the metadata for the given locale.
+    pub fn localized_uri(&self, locale: &str) -> Result<Url, url::ParseError> {
+        Url::parse(&self.uri.replace(Self::PLACEHOLDER, locale))
+    }
+}
+
+/// Converts OpenSea metadata into ERC-1155 metadata.
+///
+/// [`Metadata::properties`] is carried across, while fields with no ERC-1155 equivalent, such as
+/// [`Metadata::external_url`] and [`Metadata::image_data`], are dropped.
+impl From<Metadata> for Erc1155Metadata {
+    fn from(metadata: Metadata) -> Self {
+        Erc1155Metadata {
+            name: Some(metadata.name),
+            decimals: None,
+            description: Some(metadata.description),
+            image: metadata.image,
+            properties: metadata.properties,
+            attributes: metadata.attributes,
+            localization: None,
+        }
+    }
+}
+
+/// Converts ERC-1155 metadata into OpenSea metadata, failing if a field required by [`Metadata`] is missing.
+///
+/// [`properties`](Erc1155Metadata::properties) is carried across, while
+/// [`decimals`](Erc1155Metadata::decimals) and [`localization`](Erc1155Metadata::localization) are dropped.
+impl TryFrom<Erc1155Metadata> for Metadata {
+    type Error = BuilderError;
+
+    fn try_from(metadata: Erc1155Metadata) -> Result<Self, Self::Error> {
+        let mut builder = MetadataBuilder::new().attributes(metadata.attributes);
+        if let Some(name) = metadata.name {
+            builder = builder.name(name);
+        }
+        if let Some(description) = metadata.description {
+            builder = builder.description(description);
+        }
+        if let Some(image) = metadata.image {
+            builder = builder.image_raw(image);
+        }
+        if let Some(properties) = metadata.properties {
+            builder = builder.properties(properties);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use url::Url;
+
+    use crate::{
+        fixtures::{self, EIP_1155_EXAMPLE},
+        AttributeEntry, BuilderError, Erc1155Metadata, Localization, Metadata, RawUrl,
+        ValidationError,
+    };
+
+    #[test]
+    fn eip_1155_example() {
+        let metadata = serde_json::from_str::<Erc1155Metadata>(EIP_1155_EXAMPLE).unwrap();
+        let properties = metadata.properties.as_ref().unwrap();
+        assert_eq!(properties["simple_property"], json!("example value"));
+        assert_eq!(properties["array_property"]["value"], json!([1, 2, 3, 4]));
+
+        let round_trip = serde_json::to_string(&metadata).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&round_trip).unwrap()["image"],
+            json!("https://s3.amazonaws.com/your-bucket/images/{id}.png")
+        );
+        assert_eq!(
+            serde_json::from_str::<Erc1155Metadata>(&round_trip).unwrap(),
+            metadata
+        );
+
+        let metadata = Metadata::try_from(metadata).unwrap();
+        assert_eq!(metadata.name, "Asset Name");
+        assert!(metadata.attributes.is_empty());
+        assert_eq!(
+            Erc1155Metadata::from(metadata).image,
+            Some(RawUrl::from(
+                "https://s3.amazonaws.com/your-bucket/images/{id}.png"
+            ))
+        );
+    }
+
+    #[test]
+    fn mixed_attributes_and_properties() {
+        let value = fixtures::metadata_json_with(json!({
+            "decimals": 0,
+            "properties": { "generation": 2 },
+            "attributes": [{ "trait_type": "Core", "value": "Vortex" }]
+        }));
+        let metadata = serde_json::from_value::<Erc1155Metadata>(value).unwrap();
+        assert_eq!(metadata.decimals, Some(0));
+        assert_eq!(
+            metadata.attributes,
+            [AttributeEntry::string("Core", "Vortex")]
+        );
+
+        let metadata = Metadata::try_from(metadata).unwrap();
+        assert_eq!(
+            metadata.attributes,
+            [AttributeEntry::string("Core", "Vortex")]
+        );
+
+        assert_eq!(
+            metadata.properties.as_ref().unwrap()["generation"],
+            json!(2)
+        );
+
+        let metadata = Erc1155Metadata::from(metadata);
+        assert_eq!(metadata.name.as_deref(), Some(fixtures::NAME));
+        assert_eq!(metadata.properties.unwrap()["generation"], json!(2));
+    }
+
+    #[test]
+    fn missing_required_field() {
+        let metadata =
+            serde_json::from_str::<Erc1155Metadata>(r#"{ "name": "Asset Name" }"#).unwrap();
+        assert_eq!(
+            Metadata::try_from(metadata),
+            Err(BuilderError::MissingField("image"))
+        );
+    }
+
+    #[test]
+    fn localization() {
+        let s = r#"
+        {
+            "name": "Advertising Space",
+            "description": "Each token represents a unique Ad space in the city.",
+            "localization": {
+                "uri": "ipfs://QmWS1VAdMD353A6SDk9wNyvkT14kyCiZrNDYAad4w1tKqT/{locale}.json",
+                "default": "en",
+                "locales": ["en", "es", "fr"]
+            }
+        }
+        "#;
+        let metadata = serde_json::from_str::<Erc1155Metadata>(s).unwrap();
+        let localization = metadata.localization.as_ref().unwrap();
+        assert_eq!(localization.validate(), Ok(()));
+        assert_eq!(
+            localization.localized_uri("es"),
+            Url::parse("ipfs://QmWS1VAdMD353A6SDk9wNyvkT14kyCiZrNDYAad4w1tKqT/es.json")
+        );
+
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::from_str::<serde_json::Value>(s).unwrap()
+        );
+    }
+
+    #[test]
+    fn two_locales() {
+        let localization = Localization {
+            uri: "https://example.com/metadata/{locale}/1.json".into(),
+            default: "en".into(),
+            locales: vec!["en".into(), "de".into()],
+        };
+        assert_eq!(localization.validate(), Ok(()));
+        assert_eq!(
+            localization.localized_uri("de").unwrap().as_str(),
+            "https://example.com/metadata/de/1.json"
+        );
+
+        let value = serde_json::to_value(&localization).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "uri": "https://example.com/metadata/{locale}/1.json",
+                "default": "en",
+                "locales": ["en", "de"]
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<Localization>(value).unwrap(),
+            localization
+        );
+    }
+
+    #[test]
+    fn invalid_localization() {
+        let localization = Localization {
+            uri: "ipfs://QmWS1VAdMD353A6SDk9wNyvkT14kyCiZrNDYAad4w1tKqT/en.json".into(),
+            default: "de".into(),
+            locales: vec!["en".into(), "es".into()],
+        };
+        assert_eq!(
+            localization.validate(),
+            Err(vec![
+                ValidationError::MissingLocalePlaceholder,
+                ValidationError::UnlistedDefaultLocale("de".into()),
+            ])
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Metadata shared by the tests of several modules.
+
+use crate::{Metadata, MetadataBuilder, RawUrl};
+
+/// Name of the Rocketeer X item, which `PLANETPASS_ITEM` also describes.
+pub(crate) const NAME: &str = "Rocketeer X";
+/// Description of the Rocketeer X item.
+pub(crate) const DESCRIPTION: &str =
+    "Visit this planet and get a free Rocketeer NFT from Alucard.eth!";
+/// Image of the Rocketeer X item.
+pub(crate) const IMAGE: &str = "https://assets.wanderers.ai/file/planetpass/vid/0/0.mp4";
+
+/// The example of the [ERC-1155 metadata JSON schema](https://eips.ethereum.org/EIPS/eip-1155#erc-1155-metadata-uri-json-schema),
+/// whose `properties` also follow Enjin's format.
+#[cfg(feature = "serde")]
+pub(crate) const EIP_1155_EXAMPLE: &str = r##"
+{
+    "name": "Asset Name",
+    "description": "Lorem ipsum...",
+    "image": "https:\/\/s3.amazonaws.com\/your-bucket\/images\/{id}.png",
+    "properties": {
+        "simple_property": "example value",
+        "rich_property": {
+            "name": "Name",
+            "value": "123",
+            "display_value": "123 Example Value",
+            "class": "emphasis",
+            "css": {
+                "color": "#ffffff",
+                "font-weight": "bold",
+                "text-decoration": "underline"
+            }
+        },
+        "array_property": {
+            "name": "Name",
+            "value": [1,2,3,4],
+            "class": "emphasis"
+        }
+    }
+}
+"##;
+
+/// Returns metadata with only a name, a description and an image, for tests to fill in the rest of.
+pub(crate) fn metadata() -> Metadata {
+    Metadata {
+        name: NAME.into(),
+        description: DESCRIPTION.into(),
+        image: Some(RawUrl::from(IMAGE)),
+        ..Default::default()
+    }
+}
+
+/// Returns a builder with the fields of [`metadata`] already set.
+pub(crate) fn builder() -> MetadataBuilder {
+    MetadataBuilder::with_required(NAME, DESCRIPTION, IMAGE)
+}
+
+/// Returns the JSON of [`metadata`].
+#[cfg(feature = "serde")]
+pub(crate) fn metadata_json() -> serde_json::Value {
+    serde_json::json!({
+        "name": NAME,
+        "description": DESCRIPTION,
+        "image": IMAGE,
+    })
+}
+
+/// Returns the JSON of [`metadata`] with the members of `fields` added, replacing any of the same name.
+#[cfg(feature = "serde")]
+pub(crate) fn metadata_json_with(fields: serde_json::Value) -> serde_json::Value {
+    let mut value = metadata_json();
+    if let (Some(object), serde_json::Value::Object(fields)) = (value.as_object_mut(), fields) {
+        object.extend(fields);
+    }
+    value
+}
@@ -0,0 +1,212 @@
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use ordered_float::OrderedFloat;
+use rgb::RGB8;
+use url::Url;
+
+use crate::{
+    AttributeEntry, AttributeValue, BackgroundColor, CollectionInfo, DisplayType, Metadata, RawUrl,
+    Royalty,
+};
+
+/// Longest string generated for a free-form field, in characters.
+const MAX_STRING_LEN: usize = 32;
+/// Most attributes or royalties generated for a single item.
+const MAX_ENTRIES: usize = 8;
+
+const HOSTS: &[&str] = &[
+    "assets.wanderers.ai",
+    "example.com",
+    "ipfs.io",
+    "arweave.net",
+];
+const EXTENSIONS: &[&str] = &["png", "gif", "svg", "mp4", "glb", "json"];
+
+fn bounded_string(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(<&str>::arbitrary(u)?.chars().take(MAX_STRING_LEN).collect())
+}
+
+/// Generates a path segment of ASCII letters and digits, so that it needs no percent-encoding.
+fn segment(u: &mut Unstructured<'_>) -> Result<String> {
+    let len = u.int_in_range(1..=12)?;
+    (0..len)
+        .map(|_| {
+            u.choose(b"abcdefghijklmnopqrstuvwxyz0123456789")
+                .map(|&c| char::from(c))
+        })
+        .collect()
+}
+
+fn url(u: &mut Unstructured<'_>) -> Result<Url> {
+    let url = format!(
+        "https://{}/{}/{}.{}",
+        u.choose(HOSTS)?,
+        segment(u)?,
+        segment(u)?,
+        u.choose(EXTENSIONS)?
+    );
+    Ok(Url::parse(&url).expect("generated URLs are valid"))
+}
+
+fn raw_url(u: &mut Unstructured<'_>) -> Result<RawUrl> {
+    if u.ratio(1, 4)? {
+        Ok(RawUrl(format!("ipfs://{}/{}", segment(u)?, segment(u)?)))
+    } else {
+        url(u).map(RawUrl::from)
+    }
+}
+
+fn address(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(format!("0x{}", hex::encode(<[u8; 20]>::arbitrary(u)?)))
+}
+
+fn optional<'a, T>(
+    u: &mut Unstructured<'a>,
+    f: impl FnOnce(&mut Unstructured<'a>) -> Result<T>,
+) -> Result<Option<T>> {
+    if bool::arbitrary(u)? {
+        f(u).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn entries<'a, T>(
+    u: &mut Unstructured<'a>,
+    mut f: impl FnMut(&mut Unstructured<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    let len = u.int_in_range(0..=MAX_ENTRIES)?;
+    (0..len).map(|_| f(u)).collect()
+}
+
+/// Generates metadata that survives a round trip through JSON.
+///
+/// URLs are real `https://` or `ipfs://` URLs, strings are at most 32 characters, and an image is always
+/// present unless there is image data. `properties` and `extra` are left empty.
+impl<'a> Arbitrary<'a> for Metadata {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let image_data = optional(u, |u| Ok(format!("<svg>{}</svg>", segment(u)?)))?;
+        let image = match image_data {
+            Some(_) => optional(u, raw_url)?,
+            None => Some(raw_url(u)?),
+        };
+
+        let background_color = optional(u, |u| {
+            let [r, g, b] = <[u8; 3]>::arbitrary(u)?;
+            Ok((BackgroundColor(RGB8 { r, g, b }), Option::arbitrary(u)?))
+        })?;
+
+        Ok(Metadata {
+            name: bounded_string(u)?,
+            description: bounded_string(u)?,
+            image,
+            image_data,
+            external_url: optional(u, url)?,
+            attributes: entries(u, AttributeEntry::arbitrary)?,
+            background_color: background_color.map(|(color, _)| color),
+            background_color_alpha: background_color.and_then(|(_, alpha)| alpha),
+            animation_url: optional(u, raw_url)?,
+            youtube_url: optional(u, url)?,
+            collection: optional(u, |u| {
+                Ok(CollectionInfo {
+                    name: bounded_string(u)?,
+                    family: optional(u, bounded_string)?,
+                })
+            })?,
+            royalties: entries(u, |u| {
+                Ok(Royalty::new(address(u)?, u.int_in_range(0..=10_000)?))
+            })?,
+            seller_fee_basis_points: optional(u, |u| u.int_in_range(0..=10_000))?,
+            fee_recipient: optional(u, address)?,
+            #[cfg(feature = "serde")]
+            properties: None,
+            #[cfg(feature = "serde")]
+            extra: BTreeMap::new(),
+        })
+    }
+}
+
+/// Generates an attribute that survives a round trip through JSON, leaving `extra` empty.
+///
+/// Floats are always finite and fractional, since integral floats are read back as integers.
+impl<'a> Arbitrary<'a> for AttributeEntry {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let value = match u.int_in_range(0..=3)? {
+            0 => AttributeValue::Bool(bool::arbitrary(u)?),
+            1 => AttributeValue::String(bounded_string(u)?),
+            2 => AttributeValue::Integer(i64::arbitrary(u)?),
+            _ => {
+                let quarters = f64::from(u.int_in_range(1..=3)?) / 4.0;
+                AttributeValue::Float(OrderedFloat(f64::from(i32::arbitrary(u)?) + quarters))
+            }
+        };
+
+        Ok(AttributeEntry {
+            trait_type: optional(u, bounded_string)?,
+            value,
+            max_value: optional(u, i64::arbitrary)?,
+            display_type: optional(u, DisplayType::arbitrary)?,
+            #[cfg(feature = "serde")]
+            extra: BTreeMap::new(),
+        })
+    }
+}
+
+/// Generates one of the known display types, or an [`Other`](DisplayType::Other) that does not collide with
+/// them.
+impl<'a> Arbitrary<'a> for DisplayType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => DisplayType::Number,
+            1 => DisplayType::BoostPercentage,
+            2 => DisplayType::BoostNumber,
+            3 => DisplayType::Date,
+            _ => DisplayType::from(bounded_string(u)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use proptest::prelude::*;
+
+    use crate::{DisplayType, Metadata};
+
+    #[test]
+    fn empty_input() {
+        let metadata = Metadata::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert!(metadata.image.is_some());
+        assert!(metadata.attributes.is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn display_type_other(bytes in proptest::collection::vec(any::<u8>(), 0..64)) {
+            let display_type = DisplayType::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            prop_assert_eq!(DisplayType::from(display_type.as_str().to_owned()), display_type);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    proptest! {
+        #[test]
+        fn json_round_trip(bytes in proptest::collection::vec(any::<u8>(), 0..2048)) {
+            let metadata = Metadata::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            let s = serde_json::to_string(&metadata).unwrap();
+            prop_assert_eq!(serde_json::from_str::<Metadata>(&s).unwrap(), metadata);
+        }
+    }
+
+    #[cfg(feature = "cbor")]
+    proptest! {
+        #[test]
+        fn cbor_round_trip(bytes in proptest::collection::vec(any::<u8>(), 0..2048)) {
+            let metadata = Metadata::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            let cbor = metadata.to_cbor().unwrap();
+            prop_assert_eq!(Metadata::from_cbor(&cbor).unwrap(), metadata);
+        }
+    }
+}
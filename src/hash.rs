@@ -0,0 +1,63 @@
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::Metadata;
+
+impl Metadata {
+    /// Returns the SHA-256 hash of the metadata's canonical JSON, so that identical metadata can be detected
+    /// regardless of key or attribute order.
+    ///
+    /// The canonical JSON is compact, with the attributes [normalized](Metadata::normalize) and the keys of
+    /// every object sorted.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let value = serde_json::to_value(self.clone().normalized())
+            .expect("metadata should serialize to JSON");
+        let json = canonicalize(value).to_string();
+        Sha256::digest(json.as_bytes()).into()
+    }
+}
+
+/// Sorts the keys of every object in `value`, even if `serde_json` preserves insertion order.
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(canonicalize).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AttributeEntry, Metadata};
+
+    #[test]
+    fn content_hash() {
+        let core = AttributeEntry::string("Core", "Vortex");
+        let level = AttributeEntry::number("Level", 5);
+        let a = Metadata {
+            attributes: vec![core.clone(), level.clone()],
+            ..crate::fixtures::metadata()
+        };
+        let b = Metadata {
+            attributes: vec![level, core],
+            ..crate::fixtures::metadata()
+        };
+        assert_ne!(a, b);
+        assert_eq!(a.content_hash(), b.content_hash());
+
+        let renamed = Metadata {
+            name: "Rocketeer Y".into(),
+            ..a.clone()
+        };
+        assert_ne!(renamed.content_hash(), a.content_hash());
+    }
+}
@@ -0,0 +1,523 @@
+use std::fmt;
+
+use url::Url;
+
+use crate::{Metadata, RawUrl};
+
+/// The scheme of IPFS URLs, such as `ipfs://<cid>/<path>`.
+const SCHEME: &str = "ipfs://";
+
+/// Hosts of public HTTP gateways whose URLs [`Metadata::canonicalize_ipfs`] rewrites. Subdomain gateways are
+/// listed by the part after `<cid>.ipfs.`.
+const KNOWN_GATEWAYS: &[&str] = &[
+    "ipfs.io",
+    "dweb.link",
+    "cloudflare-ipfs.com",
+    "gateway.pinata.cloud",
+    "nftstorage.link",
+    "w3s.link",
+];
+
+/// The alphabet of base58btc, used by version 0 CIDs.
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The alphabet of lowercase base32, used by version 1 CIDs.
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// An IPFS [content identifier](https://docs.ipfs.tech/concepts/content-addressing/), as found in IPFS URLs.
+///
+/// Only the string forms used in URLs are accepted: version 0 CIDs, which are base58btc SHA-256 multihashes
+/// starting with `Qm`, and version 1 CIDs in lowercase base32, which start with `b`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cid(String);
+
+impl Cid {
+    /// Parses and validates a CID, returning `None` if it is malformed.
+    pub fn parse(cid: &str) -> Option<Cid> {
+        let valid = if cid.starts_with("Qm") {
+            base58_decode(cid).is_some_and(|bytes| bytes.len() == 34 && bytes[..2] == [0x12, 0x20])
+        } else if let Some(data) = cid.strip_prefix('b') {
+            base32_decode(data).is_some_and(|bytes| is_cid_v1(&bytes))
+        } else {
+            false
+        };
+        valid.then(|| Cid(cid.to_owned()))
+    }
+
+    /// Returns the version of the CID, which is either 0 or 1.
+    pub fn version(&self) -> u8 {
+        if self.0.starts_with("Qm") {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Returns the CID as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Cid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Decodes base58btc, returning `None` on a character outside the alphabet.
+fn base58_decode(s: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for c in s.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&digit| digit == c)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    Some([vec![0; zeros], bytes].concat())
+}
+
+/// Decodes unpadded lowercase base32, returning `None` on a character outside the alphabet.
+fn base32_decode(s: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(s.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in s.bytes() {
+        buffer = buffer << 5 | BASE32_ALPHABET.iter().position(|&digit| digit == c)? as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+/// Reads an unsigned varint from the start of `bytes`, advancing past it.
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0;
+    for shift in (0..63).step_by(7) {
+        let (&byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Checks that `bytes` are a version 1 CID: the version, a content codec and a multihash whose digest has the
+/// declared length.
+fn is_cid_v1(mut bytes: &[u8]) -> bool {
+    let header = (|| {
+        let version = read_varint(&mut bytes)?;
+        let _codec = read_varint(&mut bytes)?;
+        let _hash = read_varint(&mut bytes)?;
+        Some((version, read_varint(&mut bytes)?))
+    })();
+    matches!(header, Some((1, len)) if len == bytes.len() as u64 && len > 0)
+}
+
+/// Extracts the CID from an IPFS URL.
+///
+/// This recognizes `ipfs://<cid>/<path>`, the legacy `ipfs://ipfs/<cid>/<path>`, path gateway URLs such as
+/// `https://ipfs.io/ipfs/<cid>/<path>` and subdomain gateway URLs such as `https://<cid>.ipfs.dweb.link/<path>`.
+/// Returns `None` for any other URL, or if the CID is malformed.
+pub fn extract_cid(uri: &str) -> Option<Cid> {
+    ipfs_parts(uri).map(|(cid, _)| cid)
+}
+
+/// Splits an IPFS URL of any form recognized by [`extract_cid`] into its CID and the path after it, such as
+/// `/0.png`, which is empty if there is none. Any query or fragment is dropped.
+fn ipfs_parts(uri: &str) -> Option<(Cid, String)> {
+    if let Some(path) = ipfs_path(uri) {
+        let path = path.split(['?', '#']).next()?;
+        let (cid, rest) = path.find('/').map_or((path, ""), |end| path.split_at(end));
+        return Some((Cid::parse(cid)?, rest.to_owned()));
+    }
+
+    let url = Url::parse(uri).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    if let Some((cid, _)) = url.host_str()?.split_once(".ipfs.") {
+        return Some((Cid::parse(cid)?, url.path().to_owned()));
+    }
+    let path = url.path().strip_prefix("/ipfs/")?;
+    let (cid, rest) = path.find('/').map_or((path, ""), |end| path.split_at(end));
+    Some((Cid::parse(cid)?, rest.to_owned()))
+}
+
+/// Like [`ipfs_parts`], but only accepts HTTP URLs on one of [`KNOWN_GATEWAYS`] or `gateways`, so that a URL
+/// such as `https://assets.wanderers.ai/ipfs/<cid>/0.png` is not mistaken for a gateway URL.
+fn gateway_parts(uri: &str, gateways: &[&str]) -> Option<(Cid, String)> {
+    if ipfs_path(uri).is_none() {
+        let url = Url::parse(uri).ok()?;
+        let host = url.host_str()?;
+        let gateway = host
+            .split_once(".ipfs.")
+            .map_or(host, |(_, gateway)| gateway);
+        if !KNOWN_GATEWAYS
+            .iter()
+            .chain(gateways)
+            .any(|known| known.eq_ignore_ascii_case(gateway))
+        {
+            return None;
+        }
+    }
+    ipfs_parts(uri)
+}
+
+/// Returns the `<cid>/<path>` part of an `ipfs://` URL.
+///
+/// The legacy `ipfs://ipfs/<cid>/<path>` form is also accepted.
+fn ipfs_path(uri: &str) -> Option<&str> {
+    let path = uri.strip_prefix(SCHEME)?;
+    Some(path.strip_prefix("ipfs/").unwrap_or(path))
+}
+
+/// Rewrites an `ipfs://<cid>/<path>` URL to `<gateway>/ipfs/<cid>/<path>`.
+///
+/// Returns `None` if `uri` is not an IPFS URL.
+pub fn ipfs_to_gateway(uri: &str, gateway: &Url) -> Option<Result<Url, url::ParseError>> {
+    let path = ipfs_path(uri)?;
+    Some(join_gateway(gateway, &format!("ipfs/{}", path)))
+}
+
+/// Joins `path` onto `gateway`, treating the gateway as a directory even without a trailing slash.
+pub(crate) fn join_gateway(gateway: &Url, path: &str) -> Result<Url, url::ParseError> {
+    let mut gateway = gateway.clone();
+    if !gateway.path().ends_with('/') {
+        gateway.set_path(&format!("{}/", gateway.path()));
+    }
+    gateway.join(path)
+}
+
+impl Metadata {
+    /// Returns every distinct CID referenced by a URL of the metadata, along with the name of the first field
+    /// it appears in, such as `"image"`. See [`extract_cid`].
+    ///
+    /// This covers [`image`](Metadata::image), [`external_url`](Metadata::external_url),
+    /// [`animation_url`](Metadata::animation_url) and [`youtube_url`](Metadata::youtube_url), which is useful
+    /// for knowing what to pin.
+    pub fn ipfs_cids(&self) -> Vec<(&'static str, Cid)> {
+        let urls = [
+            ("image", self.image.as_deref()),
+            ("external_url", self.external_url.as_ref().map(Url::as_str)),
+            ("animation_url", self.animation_url.as_deref()),
+            ("youtube_url", self.youtube_url.as_ref().map(Url::as_str)),
+        ];
+        let mut cids: Vec<(&'static str, Cid)> = Vec::new();
+        for (field, url) in urls {
+            if let Some(cid) = url.and_then(extract_cid) {
+                if !cids.iter().any(|(_, seen)| *seen == cid) {
+                    cids.push((field, cid));
+                }
+            }
+        }
+        cids
+    }
+
+    /// Rewrites every IPFS URL of [`image`](Metadata::image), [`animation_url`](Metadata::animation_url) and
+    /// [`external_url`](Metadata::external_url) to the canonical `ipfs://<cid>/<path>` form, returning how many
+    /// fields changed.
+    ///
+    /// Only URLs on well-known public gateways, such as `ipfs.io` and `dweb.link`, are rewritten; use
+    /// [`canonicalize_ipfs_with`](Metadata::canonicalize_ipfs_with) to recognize other gateways. Any query or
+    /// fragment is dropped. Other URLs are left untouched.
+    pub fn canonicalize_ipfs(&mut self) -> usize {
+        self.canonicalize_ipfs_with(&[])
+    }
+
+    /// Like [`canonicalize_ipfs`](Metadata::canonicalize_ipfs), but also rewrites URLs on the given gateway
+    /// hosts, such as `"gateway.wanderers.ai"`.
+    pub fn canonicalize_ipfs_with(&mut self, gateways: &[&str]) -> usize {
+        self.replace_urls(|url| {
+            let (cid, path) = gateway_parts(url, gateways)?;
+            Url::parse(&format!("{}{}{}", SCHEME, cid, path)).ok()
+        })
+    }
+
+    /// Rewrites every IPFS URL of [`image`](Metadata::image), [`animation_url`](Metadata::animation_url) and
+    /// [`external_url`](Metadata::external_url) to use the HTTP gateway `base`, returning how many fields
+    /// changed.
+    ///
+    /// This undoes [`canonicalize_ipfs`](Metadata::canonicalize_ipfs), also moving URLs on the well-known
+    /// public gateways over to `base`. Paths are preserved, while queries and fragments are dropped. Other
+    /// URLs are left untouched.
+    pub fn with_ipfs_gateway(&mut self, base: &Url) -> usize {
+        let gateways = [base.host_str().unwrap_or_default()];
+        self.replace_urls(|url| {
+            let (cid, path) = gateway_parts(url, &gateways)?;
+            join_gateway(base, &format!("ipfs/{}{}", cid, path)).ok()
+        })
+    }
+
+    /// Applies [`rewrite_urls`](Metadata::rewrite_urls) in place, returning how many URLs changed.
+    fn replace_urls(&mut self, rewrite: impl Fn(&str) -> Option<Url>) -> usize {
+        let rewritten = self.rewrite_urls(rewrite);
+        let changed = usize::from(rewritten.image != self.image)
+            + usize::from(rewritten.animation_url != self.animation_url)
+            + usize::from(rewritten.external_url != self.external_url);
+        *self = rewritten;
+        changed
+    }
+
+    /// Returns a copy of the metadata with every `ipfs://` URL rewritten to use an HTTP `gateway`, such as
+    /// `https://ipfs.io/`.
+    ///
+    /// This covers [`image`](Metadata::image), [`animation_url`](Metadata::animation_url) and
+    /// [`external_url`](Metadata::external_url). Other URLs, and IPFS URLs that cannot be rewritten, are
+    /// left untouched. See [`ipfs_to_gateway`].
+    pub fn to_gateway(&self, gateway: &Url) -> Metadata {
+        self.rewrite_urls(|url| ipfs_to_gateway(url, gateway).and_then(Result::ok))
+    }
+
+    /// Returns a copy of the metadata with the image, animation and external URLs replaced wherever
+    /// `rewrite` returns a new URL.
+    pub(crate) fn rewrite_urls(&self, rewrite: impl Fn(&str) -> Option<Url>) -> Metadata {
+        let rewrite_raw = |url: &RawUrl| {
+            rewrite(url)
+                .map(RawUrl::from)
+                .unwrap_or_else(|| url.clone())
+        };
+
+        Metadata {
+            image: self.image.as_ref().map(rewrite_raw),
+            animation_url: self.animation_url.as_ref().map(rewrite_raw),
+            external_url: self
+                .external_url
+                .as_ref()
+                .map(|url| rewrite(url.as_str()).unwrap_or_else(|| url.clone())),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::{extract_cid, ipfs_to_gateway, Cid, Metadata, RawUrl};
+
+    const CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    const CID_V0: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+    #[test]
+    fn gateway() {
+        let gateway = Url::parse("https://ipfs.io/").unwrap();
+        let metadata = Metadata {
+            image: Some(RawUrl(format!("ipfs://{}/0.png", CID))),
+            animation_url: Some(RawUrl::from(crate::fixtures::IMAGE)),
+            ..Default::default()
+        }
+        .to_gateway(&gateway);
+
+        assert_eq!(
+            metadata.image,
+            Some(RawUrl(format!("https://ipfs.io/ipfs/{}/0.png", CID)))
+        );
+        assert_eq!(
+            metadata.animation_url,
+            Some(RawUrl::from(crate::fixtures::IMAGE))
+        );
+    }
+
+    #[test]
+    fn gateway_forms() {
+        let gateway = Url::parse("https://gateway.example.com/custom").unwrap();
+        assert_eq!(
+            ipfs_to_gateway(&format!("ipfs://ipfs/{}", CID), &gateway)
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            format!("https://gateway.example.com/custom/ipfs/{}", CID)
+        );
+        assert_eq!(ipfs_to_gateway("https://ipfs.io/", &gateway), None);
+    }
+
+    #[test]
+    fn cids() {
+        let v0 = Cid::parse(CID_V0).unwrap();
+        assert_eq!(v0.version(), 0);
+        assert_eq!(v0.as_str(), CID_V0);
+        let v1 = Cid::parse(CID).unwrap();
+        assert_eq!(v1.version(), 1);
+        assert_eq!(v1.to_string(), CID);
+
+        for invalid in [
+            "",
+            "Qm",
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd",
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0",
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd",
+            "BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI",
+            "planetpass",
+        ] {
+            assert_eq!(Cid::parse(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn extract() {
+        for (url, cid) in [
+            (format!("ipfs://{}", CID_V0), CID_V0),
+            (format!("ipfs://{}/0.png", CID), CID),
+            (format!("ipfs://ipfs/{}/0.png", CID_V0), CID_V0),
+            (format!("https://ipfs.io/ipfs/{}", CID_V0), CID_V0),
+            (
+                format!(
+                    "https://gateway.pinata.cloud/ipfs/{}/0.png?filename=0.png",
+                    CID
+                ),
+                CID,
+            ),
+            (format!("https://{}.ipfs.dweb.link/0.png", CID), CID),
+            (format!("https://{}.ipfs.nftstorage.link/", CID), CID),
+        ] {
+            assert_eq!(
+                extract_cid(&url).as_ref().map(Cid::as_str),
+                Some(cid),
+                "{}",
+                url
+            );
+        }
+
+        for url in [
+            "https://assets.wanderers.ai/file/planetpass/vid/0/0.mp4",
+            "https://ipfs.io/ipns/docs.ipfs.tech",
+            "https://ipfs.io/ipfs/not-a-cid",
+            "ar://abcd5678",
+            "0.png",
+        ] {
+            assert_eq!(extract_cid(url), None, "{}", url);
+        }
+    }
+
+    #[test]
+    fn ipfs_cids() {
+        let metadata = Metadata {
+            image: Some(RawUrl(format!("ipfs://{}/0.png", CID))),
+            external_url: Some(Url::parse("https://wanderers.ai/").unwrap()),
+            animation_url: Some(RawUrl(format!("https://{}.ipfs.dweb.link/0.mp4", CID))),
+            youtube_url: Some(Url::parse(&format!("https://ipfs.io/ipfs/{}", CID_V0)).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            metadata.ipfs_cids(),
+            [
+                ("image", Cid::parse(CID).unwrap()),
+                ("youtube_url", Cid::parse(CID_V0).unwrap()),
+            ]
+        );
+        assert_eq!(Metadata::default().ipfs_cids(), []);
+    }
+
+    #[test]
+    fn canonicalize() {
+        let mut metadata = Metadata {
+            image: Some(RawUrl(format!("https://ipfs.io/ipfs/{}/art/0.png", CID_V0))),
+            animation_url: Some(RawUrl::from(crate::fixtures::IMAGE)),
+            external_url: Some(
+                Url::parse(&format!(
+                    "https://{}.ipfs.dweb.link/index.html?token=0",
+                    CID
+                ))
+                .unwrap(),
+            ),
+            ..Default::default()
+        };
+        let original = metadata.clone();
+
+        assert_eq!(metadata.canonicalize_ipfs(), 2);
+        assert_eq!(
+            metadata.image,
+            Some(RawUrl(format!("ipfs://{}/art/0.png", CID_V0)))
+        );
+        assert_eq!(metadata.animation_url, original.animation_url);
+        assert_eq!(
+            metadata.external_url.as_ref().map(Url::as_str),
+            Some(format!("ipfs://{}/index.html", CID).as_str())
+        );
+        assert_eq!(metadata.canonicalize_ipfs(), 0);
+
+        let gateway = Url::parse("https://gateway.wanderers.ai/").unwrap();
+        assert_eq!(metadata.with_ipfs_gateway(&gateway), 2);
+        assert_eq!(
+            metadata.image,
+            Some(RawUrl(format!(
+                "https://gateway.wanderers.ai/ipfs/{}/art/0.png",
+                CID_V0
+            )))
+        );
+        assert_eq!(metadata.animation_url, original.animation_url);
+        assert_eq!(
+            metadata.external_url.as_ref().map(Url::as_str),
+            Some(format!("https://gateway.wanderers.ai/ipfs/{}/index.html", CID).as_str())
+        );
+        assert_eq!(metadata.with_ipfs_gateway(&gateway), 0);
+
+        assert_eq!(metadata.canonicalize_ipfs(), 0);
+        assert_eq!(
+            metadata.canonicalize_ipfs_with(&["gateway.wanderers.ai"]),
+            2
+        );
+        assert_eq!(
+            metadata.image,
+            Some(RawUrl(format!("ipfs://{}/art/0.png", CID_V0)))
+        );
+    }
+
+    #[test]
+    fn gateway_round_trip() {
+        let gateway = Url::parse("https://ipfs.io/").unwrap();
+        for path in ["", "/", "/0.png", "/art/0%201.png", "/a/b/c.json"] {
+            let url = format!("https://ipfs.io/ipfs/{}{}", CID, path);
+            let mut metadata = Metadata {
+                image: Some(RawUrl(url.clone())),
+                ..Default::default()
+            };
+            assert_eq!(metadata.canonicalize_ipfs(), 1, "{}", url);
+            assert_eq!(
+                metadata.image,
+                Some(RawUrl(format!("ipfs://{}{}", CID, path)))
+            );
+            assert_eq!(metadata.with_ipfs_gateway(&gateway), 1, "{}", url);
+            assert_eq!(metadata.image, Some(RawUrl(url)));
+        }
+    }
+
+    #[test]
+    fn unknown_hosts_untouched() {
+        let mut metadata = Metadata {
+            image: Some(RawUrl::from("https://example.com/ipfs/not-a-cid/0.png")),
+            animation_url: Some(RawUrl(format!(
+                "https://assets.wanderers.ai/ipfs/{}/0.mp4",
+                CID
+            ))),
+            external_url: Some(Url::parse("https://wanderers.ai/").unwrap()),
+            ..Default::default()
+        };
+        let original = metadata.clone();
+        assert_eq!(metadata.canonicalize_ipfs(), 0);
+        assert_eq!(
+            metadata.with_ipfs_gateway(&Url::parse("https://ipfs.io/").unwrap()),
+            0
+        );
+        assert_eq!(metadata, original);
+
+        assert_eq!(metadata.canonicalize_ipfs_with(&["assets.wanderers.ai"]), 1);
+        assert_eq!(
+            metadata.animation_url,
+            Some(RawUrl(format!("ipfs://{}/0.mp4", CID)))
+        );
+    }
+}
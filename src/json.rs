@@ -0,0 +1,97 @@
+use std::{fmt, str::FromStr};
+
+use serde_json::Value;
+
+use crate::Metadata;
+
+impl Metadata {
+    /// Converts the metadata into a JSON [`Value`], without serializing it to a string first.
+    pub fn to_value(&self) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+/// Deserializes metadata from a JSON [`Value`], such as one read from a database column.
+impl TryFrom<Value> for Metadata {
+    type Error = serde_json::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+/// Serializes metadata into a JSON [`Value`]. See [`Metadata::to_value`].
+impl TryFrom<Metadata> for Value {
+    type Error = serde_json::Error;
+
+    fn try_from(metadata: Metadata) -> Result<Self, Self::Error> {
+        metadata.to_value()
+    }
+}
+
+/// Parses metadata from a JSON string, as [`serde_json::from_str`] would.
+impl FromStr for Metadata {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Writes the metadata as compact JSON, the counterpart of the [`FromStr`] implementation.
+///
+/// Serialization cannot fail for metadata built through this crate, but if it does, a marker describing the
+/// error is written instead.
+impl fmt::Display for Metadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match serde_json::to_string(self) {
+            Ok(s) => f.write_str(&s),
+            Err(error) => write!(f, "<invalid metadata: {}>", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{AttributeEntry, Metadata};
+
+    #[test]
+    fn value_round_trip() {
+        let value = crate::fixtures::metadata_json_with(json!({
+            "attributes": [{ "trait_type": "Core", "value": "Vortex" }]
+        }));
+        let metadata = Metadata::try_from(value.clone()).unwrap();
+        assert_eq!(
+            metadata.attributes,
+            [AttributeEntry::string("Core", "Vortex")]
+        );
+        assert_eq!(metadata.to_value().unwrap(), value);
+        assert_eq!(Value::try_from(metadata).unwrap(), value);
+
+        assert!(Metadata::try_from(json!({ "name": crate::fixtures::NAME })).is_err());
+    }
+
+    #[test]
+    fn from_str() {
+        let metadata = crate::tests::PLANETPASS_ITEM.parse::<Metadata>().unwrap();
+        assert_eq!(metadata.name, crate::fixtures::NAME);
+        assert_eq!(metadata.string_value("Core"), Some("Vortex"));
+        assert_eq!(
+            metadata,
+            serde_json::from_str::<Metadata>(crate::tests::PLANETPASS_ITEM).unwrap()
+        );
+
+        assert!("{}".parse::<Metadata>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let metadata = crate::tests::PLANETPASS_ITEM.parse::<Metadata>().unwrap();
+        let s = format!("{}", metadata);
+        assert!(!s.contains('\n'));
+        assert_eq!(s, serde_json::to_string(&metadata).unwrap());
+        assert_eq!(s.parse::<Metadata>().unwrap(), metadata);
+    }
+}
@@ -0,0 +1,147 @@
+#![allow(deprecated)]
+
+use crate::{AttributeEntry, AttributeValue, DisplayType};
+
+/// The attribute type of prior versions, which modelled each kind of value as a separate variant.
+///
+/// It is kept so that code written against it can migrate gradually: convert into an [`AttributeEntry`] with
+/// [`From`], or back with [`TryFrom`] where an older API still expects this type.
+#[deprecated(note = "use `AttributeEntry`, which holds the value in an `AttributeValue`")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LegacyAttributeEntry {
+    /// Textual attribute.
+    String {
+        /// Name of the trait.
+        trait_type: String,
+        /// Value of the attribute.
+        value: String,
+    },
+    /// Numerical attribute.
+    Number {
+        /// Name of the trait.
+        trait_type: String,
+        /// Value of the attribute.
+        value: u64,
+        /// How the attribute should be displayed.
+        display_type: Option<DisplayType>,
+    },
+}
+
+/// Converts a legacy attribute, which cannot fail.
+///
+/// Numbers above [`i64::MAX`] become [`Float`](AttributeValue::Float), as they do when deserializing.
+impl From<LegacyAttributeEntry> for AttributeEntry {
+    fn from(attribute: LegacyAttributeEntry) -> Self {
+        match attribute {
+            LegacyAttributeEntry::String { trait_type, value } => {
+                AttributeEntry::string(trait_type, value)
+            }
+            LegacyAttributeEntry::Number {
+                trait_type,
+                value,
+                display_type,
+            } => AttributeEntry {
+                display_type,
+                ..AttributeEntry::new(
+                    trait_type,
+                    i64::try_from(value).map_or(AttributeValue::from(value as f64), Into::into),
+                )
+            },
+        }
+    }
+}
+
+/// Converts an attribute back into a legacy attribute, failing with the attribute unchanged if the legacy type
+/// cannot represent it.
+///
+/// Only attributes with a trait type and no [`max_value`](AttributeEntry::max_value) can be converted, and their
+/// value must either be textual without a display type, or integral and non-negative. Any `extra` fields are
+/// dropped.
+impl TryFrom<AttributeEntry> for LegacyAttributeEntry {
+    type Error = AttributeEntry;
+
+    fn try_from(attribute: AttributeEntry) -> Result<Self, Self::Error> {
+        if attribute.max_value.is_some() {
+            return Err(attribute);
+        }
+        let Some(trait_type) = attribute.trait_type.clone() else {
+            return Err(attribute);
+        };
+        match attribute.value {
+            AttributeValue::String(value) if attribute.display_type.is_none() => {
+                Ok(LegacyAttributeEntry::String { trait_type, value })
+            }
+            AttributeValue::Integer(value) if value >= 0 => Ok(LegacyAttributeEntry::Number {
+                trait_type,
+                value: value as u64,
+                display_type: attribute.display_type,
+            }),
+            _ => Err(attribute),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AttributeEntry, AttributeValue, DisplayType, LegacyAttributeEntry};
+
+    #[test]
+    fn from_legacy() {
+        let cases = [
+            (
+                LegacyAttributeEntry::String {
+                    trait_type: "Core".into(),
+                    value: "Vortex".into(),
+                },
+                AttributeEntry::string("Core", "Vortex"),
+            ),
+            (
+                LegacyAttributeEntry::Number {
+                    trait_type: "Level".into(),
+                    value: 3,
+                    display_type: Some(DisplayType::BoostNumber),
+                },
+                AttributeEntry::boost_number("Level", 3),
+            ),
+            (
+                LegacyAttributeEntry::Number {
+                    trait_type: "Level".into(),
+                    value: 3,
+                    display_type: None,
+                },
+                AttributeEntry::new("Level", 3),
+            ),
+        ];
+        for (legacy, attribute) in cases {
+            assert_eq!(AttributeEntry::from(legacy.clone()), attribute);
+            assert_eq!(LegacyAttributeEntry::try_from(attribute), Ok(legacy));
+        }
+
+        let attribute = AttributeEntry::from(LegacyAttributeEntry::Number {
+            trait_type: "Supply".into(),
+            value: u64::MAX,
+            display_type: None,
+        });
+        assert_eq!(attribute.value, AttributeValue::from(u64::MAX as f64));
+    }
+
+    #[test]
+    fn into_legacy() {
+        for attribute in [
+            AttributeEntry::generic("Happy"),
+            AttributeEntry::new("Level", -3),
+            AttributeEntry::float("Speed", 1.5),
+            AttributeEntry::bool("Legendary", true),
+            AttributeEntry::number_with_max("Level", 3, 5),
+            AttributeEntry {
+                display_type: Some(DisplayType::Date),
+                ..AttributeEntry::string("Birthday", "2021-06-01")
+            },
+        ] {
+            assert_eq!(
+                LegacyAttributeEntry::try_from(attribute.clone()),
+                Err(attribute)
+            );
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use serde_json::{Number, Value};
+
+use crate::{DisplayType, Metadata};
+
+impl Metadata {
+    /// Parses metadata from a JSON string, correcting common generator mistakes before deserializing.
+    ///
+    /// Currently, attribute values encoded as strings (such as `"42"`) are coerced into numbers when the
+    /// attribute's `display_type` is one of the numerical [`DisplayType`]s. Strict parsing through
+    /// [`Deserialize`](serde::Deserialize) remains the default and performs no such coercion.
+    pub fn from_str_lenient(s: &str) -> Result<Metadata, serde_json::Error> {
+        let mut value = serde_json::from_str::<Value>(s)?;
+        normalize(&mut value);
+        serde_json::from_value(value)
+    }
+}
+
+fn normalize(metadata: &mut Value) {
+    for key in ["attributes", "traits"] {
+        if let Some(Value::Array(attributes)) = metadata.get_mut(key) {
+            attributes.iter_mut().for_each(coerce_numeric_string);
+        }
+    }
+}
+
+fn coerce_numeric_string(attribute: &mut Value) {
+    let numeric = attribute
+        .get("display_type")
+        .or_else(|| attribute.get("displayType"))
+        .and_then(Value::as_str)
+        .map(|display_type| {
+            matches!(
+                DisplayType::from(display_type.to_owned()),
+                DisplayType::Number
+                    | DisplayType::BoostNumber
+                    | DisplayType::BoostPercentage
+                    | DisplayType::Date
+            )
+        })
+        .unwrap_or(false);
+    if !numeric {
+        return;
+    }
+
+    if let Some(value) = attribute.get_mut("value") {
+        let number = value.as_str().map(str::trim).and_then(|s| {
+            s.parse::<i64>().map(Value::from).ok().or_else(|| {
+                s.parse::<f64>()
+                    .ok()
+                    .and_then(Number::from_f64)
+                    .map(Value::Number)
+            })
+        });
+        if let Some(number) = number {
+            *value = number;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{fixtures, AttributeEntry, AttributeValue, DisplayType, Metadata};
+
+    fn numeric_strings() -> String {
+        fixtures::metadata_json_with(json!({
+            "attributes": [
+                { "trait_type": "Level", "display_type": "number", "value": "42" },
+                { "trait_type": "Stamina Increase", "display_type": "boost_percentage", "value": "1.5" },
+                { "trait_type": "Serial", "value": "42" },
+                { "trait_type": "Speed", "display_type": "number", "value": "fast" }
+            ]
+        }))
+        .to_string()
+    }
+
+    #[test]
+    fn coerce_numeric_strings() {
+        let metadata = Metadata::from_str_lenient(&numeric_strings()).unwrap();
+        assert_eq!(metadata.attributes[0], AttributeEntry::number("Level", 42));
+        assert_eq!(
+            metadata.attributes[1],
+            AttributeEntry {
+                display_type: Some(DisplayType::BoostPercentage),
+                ..AttributeEntry::float("Stamina Increase", 1.5)
+            }
+        );
+        assert_eq!(
+            metadata.attributes[2],
+            AttributeEntry::string("Serial", "42")
+        );
+        assert_eq!(
+            metadata.attributes[3].value,
+            AttributeValue::String("fast".into())
+        );
+
+        let s = serde_json::to_string(&metadata).unwrap();
+        assert!(s.contains(r#""value":42"#));
+    }
+
+    #[test]
+    fn strict_by_default() {
+        let metadata = serde_json::from_str::<Metadata>(&numeric_strings()).unwrap();
+        assert_eq!(
+            metadata.attributes[0].value,
+            AttributeValue::String("42".into())
+        );
+    }
+
+    #[test]
+    fn coerce_only_with_numerical_display_type() {
+        let s = fixtures::metadata_json_with(json!({
+            "traits": [
+                { "trait_type": "Level", "display_type": "number", "value": "5" },
+                { "traitType": "Generation", "displayType": "number", "value": " 5 " },
+                { "trait_type": "Core", "display_type": "number", "value": "Vortex" },
+                { "trait_type": "Serial", "value": "5" }
+            ]
+        }))
+        .to_string();
+        let metadata = Metadata::from_str_lenient(&s).unwrap();
+        assert_eq!(metadata.attributes[0], AttributeEntry::number("Level", 5));
+        assert_eq!(
+            metadata.attributes[1],
+            AttributeEntry::number("Generation", 5)
+        );
+        assert_eq!(
+            metadata.attributes[2].value,
+            AttributeValue::String("Vortex".into())
+        );
+        assert_eq!(
+            metadata.attributes[3],
+            AttributeEntry::string("Serial", "5")
+        );
+    }
+}
@@ -4,77 +4,723 @@
 //!
 //! While [EIP-721](https://eips.ethereum.org/EIPS/eip-721#specification) defines a "ERC-721 Metadata JSON Schema", in practice
 //! it is rarely used. Instead, this crate implements the more popular [OpenSea metadata standard](https://docs.opensea.io/docs/metadata-standards).
+//! [`Eip721Metadata`] can be used to convert to and from the EIP-721 schema.
 //!
 //! This crate does not attempt to perform validation more than what is strictly necessary. Since every secondary
 //! market will use the fields in the metadata in a different way, it is up to the crate consumer to make sure the fields are appropriately populated.
 
-use rgb::RGB8;
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::fmt;
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use ordered_float::OrderedFloat;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+mod arweave;
+#[cfg(feature = "borsh")]
+mod borsh_with;
+mod builder;
+#[cfg(feature = "cbor")]
+mod cbor;
+mod collection;
+mod color;
+mod contract;
+#[cfg(feature = "data-uri")]
+mod data_uri;
+#[cfg(feature = "chrono")]
+mod date;
+mod eip721;
+#[cfg(feature = "serde")]
+mod enjin;
+#[cfg(feature = "serde")]
+mod erc1155;
+#[cfg(test)]
+mod fixtures;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+#[cfg(feature = "hash")]
+mod hash;
+mod ipfs;
+#[cfg(feature = "serde")]
+mod json;
+mod legacy;
+#[cfg(feature = "serde")]
+mod lenient;
+mod media;
+#[cfg(feature = "serde")]
+mod metaplex;
+#[cfg(feature = "validate-net")]
+mod net;
+#[cfg(feature = "serde")]
+mod partial;
+mod profile;
+#[cfg(feature = "serde")]
+mod properties;
+mod raw_url;
+#[cfg(feature = "serde")]
+mod repr;
+mod royalty;
+mod sanitize;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(feature = "serde")]
+mod strict;
+mod token_id;
+mod url_policy;
+mod validate;
+mod youtube;
+
+pub use arweave::arweave_to_gateway;
+pub use builder::{BuilderError, IntoUrl, MetadataBuilder};
+pub use collection::{Collection, CollectionInfo};
+pub use color::{color_to_hex, parse_hex_color, BackgroundColor, ColorError};
+pub use contract::ContractMetadata;
+#[cfg(feature = "data-uri")]
+pub use data_uri::DataUri;
+pub use eip721::Eip721Metadata;
+#[cfg(feature = "serde")]
+pub use enjin::{EnjinProperties, EnjinProperty, RichProperty};
+#[cfg(feature = "serde")]
+pub use erc1155::{Erc1155Metadata, Localization};
+pub use ipfs::{extract_cid, ipfs_to_gateway, Cid};
+#[allow(deprecated)]
+pub use legacy::LegacyAttributeEntry;
+#[cfg(feature = "serde")]
+pub use metaplex::{Creator, MetaplexFile, MetaplexMetadata, MetaplexProperties};
+#[cfg(feature = "validate-net")]
+pub use net::{NetworkError, NetworkValidator, UrlReport};
+pub use ordered_float;
+#[cfg(feature = "serde")]
+pub use partial::PartialMetadata;
+pub use profile::{ProfileLimits, ValidationProfile};
+#[cfg(feature = "serde")]
+pub use properties::Properties;
+pub use raw_url::RawUrl;
+pub use royalty::Royalty;
+pub use sanitize::sanitize;
+#[cfg(feature = "serde")]
+pub use ser::SerializeProfile;
+#[cfg(feature = "serde")]
+pub use strict::StrictMetadata;
+pub use token_id::{expand_id_placeholder, TokenId};
+pub use url_policy::{UrlPolicy, UrlRule, UrlViolation};
+pub use validate::ValidationError;
+
 /// Metadata for a token.
 ///
 /// While even an empty object is "valid" metadata, this crate takes a more opinionated approach.
-/// The following fields are strictly required: [`name`](Metadata::name), [`description`](Metadata::description), [`image`](Metadata::image).
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// The following fields are strictly required: [`name`](Metadata::name), [`description`](Metadata::description),
+/// and at least one of [`image`](Metadata::image) and [`image_data`](Metadata::image_data).
+///
+/// When deserializing, `image_url` is accepted in place of `image`, and the optional fields are also accepted in
+/// camelCase, such as `externalUrl`. The snake_case spelling takes precedence when both are present, and
+/// serialization always uses snake_case. An empty string in [`external_url`](Metadata::external_url),
+/// [`animation_url`](Metadata::animation_url) or [`youtube_url`](Metadata::youtube_url) is read as absent.
+#[cfg_attr(
+    feature = "serde",
+    doc = "",
+    doc = "Keys are serialized in the order of [`SerializeProfile::Default`], and [`Metadata::to_string_with`] \
+           uses the order of another [`SerializeProfile`]."
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize),
+    serde(try_from = "repr::MetadataRepr")
+)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Metadata {
-    /// URL to image of the item.
-    pub image: Url,
-    /// External URL to another site.
-    pub external_url: Option<Url>,
-    /// Human-readable description of the item.
-    pub description: String,
     /// Name of the item.
     pub name: String,
+    /// Human-readable description of the item.
+    pub description: String,
+    /// URL to image of the item, which may be relative until [resolved](Metadata::resolve_urls).
+    pub image: Option<RawUrl>,
+    /// Raw SVG image data of the item, used by fully on-chain projects instead of an [`image`](Metadata::image) URL.
+    pub image_data: Option<String>,
+    /// External URL to another site.
+    ///
+    /// `external_link` is also accepted when deserializing, although `external_url` takes precedence.
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub external_url: Option<Url>,
     /// Attributes for the item.
-    #[cfg_attr(feature = "serde", serde(default))]
+    ///
+    /// `traits` is also accepted when deserializing, and its entries are appended to those of `attributes`
+    /// unless already present. Either may be an object mapping each trait type to its value, as used by some
+    /// older collections, although serialization always produces an array. `null` is treated as no attributes.
     pub attributes: Vec<AttributeEntry>,
     /// Background color of the item.
     /// When serialized, it takes the form of a 6-character hexadecimal string without a `#`.
-    #[cfg_attr(feature = "serde", serde(with = "rgb8_fromhex_opt", default))]
-    pub background_color: Option<RGB8>,
-    /// URL to multi-media attachment for the item.
-    pub animation_url: Option<Url>,
+    pub background_color: Option<BackgroundColor>,
+    /// Alpha channel of the [`background_color`](Metadata::background_color), where `0` is fully transparent, if
+    /// it has one.
+    ///
+    /// It is read from and written as the last 2 digits of an 8-digit `background_color` (`rrggbbaa`), and is
+    /// ignored if there is no background color.
+    pub background_color_alpha: Option<u8>,
+    /// URL to multi-media attachment for the item, which may be relative until
+    /// [resolved](Metadata::resolve_urls).
+    pub animation_url: Option<RawUrl>,
     /// URL to a YouTube video.
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
     pub youtube_url: Option<Url>,
+    /// Collection the item belongs to.
+    pub collection: Option<CollectionInfo>,
+    /// Royalties paid on secondary sales, as used by Rarible.
+    pub royalties: Vec<Royalty>,
+    /// Royalty hint for marketplaces, in basis points, where 100 is a 1% fee.
+    pub seller_fee_basis_points: Option<u32>,
+    /// Address that the [`seller_fee_basis_points`](Metadata::seller_fee_basis_points) royalty is paid to.
+    pub fee_recipient: Option<String>,
+    /// Free-form ERC-1155 properties of the item, distinct from the [`attributes`](Metadata::attributes) array.
+    #[cfg(feature = "serde")]
+    pub properties: Option<Properties>,
+    /// Fields not otherwise recognized, preserved so that they survive a round trip.
+    ///
+    /// This is a [`BTreeMap`] rather than a `HashMap` so that `Metadata` remains [`Hash`].
+    #[cfg(feature = "serde")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::json::serialize",
+            deserialize_with = "crate::borsh_with::json::deserialize"
+        )
+    )]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl Metadata {
+    /// Creates a [`MetadataBuilder`] for incrementally constructing metadata.
+    pub fn builder() -> MetadataBuilder {
+        MetadataBuilder::new()
+    }
+
+    /// Returns the image of the item, preferring [`image`](Metadata::image) over
+    /// [`image_data`](Metadata::image_data).
+    pub fn primary_image(&self) -> Option<Image<'_>> {
+        self.image
+            .as_ref()
+            .map(Image::Url)
+            .or_else(|| self.image_data.as_deref().map(Image::Data))
+    }
+
+    /// Joins a relative [`image`](Metadata::image) and [`animation_url`](Metadata::animation_url) against
+    /// `base`, such as the URL the metadata was fetched from.
+    ///
+    /// Absolute URLs are left exactly as written. Nothing is modified if any URL fails to join.
+    pub fn resolve_urls(&mut self, base: &Url) -> Result<(), url::ParseError> {
+        let resolve = |url: &RawUrl| match url.is_relative() {
+            true => base.join(url).map(RawUrl::from),
+            false => Ok(url.clone()),
+        };
+        let image = self.image.as_ref().map(resolve).transpose()?;
+        let animation_url = self.animation_url.as_ref().map(resolve).transpose()?;
+
+        self.image = image;
+        self.animation_url = animation_url;
+        Ok(())
+    }
+
+    /// Returns the first attribute with the given trait type.
+    pub fn attribute(&self, trait_type: &str) -> Option<&AttributeEntry> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.trait_type() == Some(trait_type))
+    }
+
+    /// Returns the value of the first attribute with the given trait type, if it is textual.
+    pub fn string_value(&self, trait_type: &str) -> Option<&str> {
+        match &self.attribute(trait_type)?.value {
+            AttributeValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the first attribute with the given trait type, if it is integral.
+    pub fn number_value(&self, trait_type: &str) -> Option<i64> {
+        match self.attribute(trait_type)?.value {
+            AttributeValue::Integer(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the numerical attributes with the given display type, in order.
+    ///
+    /// Attributes with a textual or boolean value are never returned, even if they have a display type.
+    pub fn attributes_with_display(
+        &self,
+        display_type: DisplayType,
+    ) -> impl Iterator<Item = &AttributeEntry> + '_ {
+        self.attributes.iter().filter(move |attribute| {
+            attribute.display_type.as_ref() == Some(&display_type)
+                && matches!(
+                    attribute.value,
+                    AttributeValue::Integer(_) | AttributeValue::Float(_)
+                )
+        })
+    }
+
+    /// Returns every trait type that appears on more than one attribute, in order of first duplication.
+    ///
+    /// Comparison is case-sensitive, and attributes without a trait type are ignored.
+    pub fn duplicate_trait_types(&self) -> Vec<&str> {
+        self.duplicate_trait_type_indices(TraitTypeComparison::CaseSensitive)
+            .into_iter()
+            .map(|(trait_type, _)| trait_type)
+            .collect()
+    }
+
+    /// Returns every trait type that appears on more than one attribute along with the indices of those
+    /// attributes, in order of first duplication.
+    ///
+    /// Trait types are compared according to `comparison`, and the spelling of the first is returned.
+    /// Attributes without a trait type are ignored.
+    pub fn duplicate_trait_type_indices(
+        &self,
+        comparison: TraitTypeComparison,
+    ) -> Vec<(&str, Vec<usize>)> {
+        let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+        let mut positions = HashMap::<String, usize>::new();
+        for (index, attribute) in self.attributes.iter().enumerate() {
+            let Some(trait_type) = attribute.trait_type() else {
+                continue;
+            };
+            match positions.entry(comparison.key(trait_type)) {
+                Entry::Occupied(position) => groups[*position.get()].1.push(index),
+                Entry::Vacant(position) => {
+                    position.insert(groups.len());
+                    groups.push((trait_type, vec![index]));
+                }
+            }
+        }
+        groups.retain(|(_, indices)| indices.len() > 1);
+        groups.sort_by_key(|(_, indices)| indices[1]);
+        groups
+    }
+
+    /// Removes attributes whose trait type already appears on another attribute, keeping the first or last
+    /// according to `strategy`, and returns how many were removed.
+    ///
+    /// Trait types are compared according to `comparison`, and attributes without a trait type are always
+    /// kept.
+    pub fn dedup_attributes(
+        &mut self,
+        strategy: DedupStrategy,
+        comparison: TraitTypeComparison,
+    ) -> usize {
+        let len = self.attributes.len();
+        let mut seen = HashSet::new();
+        let keep = |attribute: &AttributeEntry| {
+            attribute
+                .trait_type()
+                .is_none_or(|trait_type| seen.insert(comparison.key(trait_type)))
+        };
+        match strategy {
+            DedupStrategy::KeepFirst => self.attributes.retain(keep),
+            DedupStrategy::KeepLast => {
+                self.attributes.reverse();
+                self.attributes.retain(keep);
+                self.attributes.reverse();
+            }
+        }
+        len - self.attributes.len()
+    }
+
+    /// Sorts the [`attributes`](Metadata::attributes) by trait type and then by value, so that metadata
+    /// differing only in attribute order compares equal once normalized.
+    ///
+    /// This changes the order in which attributes are serialized. Generic attributes sort first.
+    #[cfg_attr(
+        feature = "serde",
+        doc = "Attributes that are otherwise equal are ordered by their [`extra`](AttributeEntry::extra) fields, \
+               compared as JSON."
+    )]
+    pub fn normalize(&mut self) {
+        fn key(attribute: &AttributeEntry) -> impl Ord + '_ {
+            (
+                attribute.trait_type(),
+                &attribute.value,
+                attribute.max_value,
+                attribute.display_type.as_ref().map(DisplayType::as_str),
+            )
+        }
+        #[cfg(feature = "serde")]
+        fn extra(attribute: &AttributeEntry) -> String {
+            serde_json::to_string(&attribute.extra).unwrap_or_default()
+        }
+
+        self.attributes.sort_by(|a, b| {
+            let ordering = key(a).cmp(&key(b));
+            #[cfg(feature = "serde")]
+            let ordering = ordering.then_with(|| extra(a).cmp(&extra(b)));
+            ordering
+        });
+    }
+
+    /// Returns the metadata [normalized](Metadata::normalize).
+    pub fn normalized(mut self) -> Self {
+        self.normalize();
+        self
+    }
+}
+
+/// Which attribute [`Metadata::dedup_attributes`] keeps when several share a trait type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DedupStrategy {
+    /// Keep the first attribute with each trait type.
+    KeepFirst,
+    /// Keep the last attribute with each trait type, as a later entry often overrides an earlier one.
+    KeepLast,
+}
+
+/// How [`Metadata::dedup_attributes`] and [`Metadata::duplicate_trait_type_indices`] compare trait types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TraitTypeComparison {
+    /// Trait types are equal only if spelled exactly the same.
+    #[default]
+    CaseSensitive,
+    /// Trait types differing only in case, such as `Level` and `level`, are equal.
+    CaseInsensitive,
+}
+
+impl TraitTypeComparison {
+    /// Returns the key under which `trait_type` is compared.
+    fn key(self, trait_type: &str) -> String {
+        match self {
+            TraitTypeComparison::CaseSensitive => trait_type.to_owned(),
+            TraitTypeComparison::CaseInsensitive => trait_type.to_lowercase(),
+        }
+    }
+}
+
+/// The image of an item, as returned by [`Metadata::primary_image`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Image<'a> {
+    /// URL to the image.
+    Url(&'a RawUrl),
+    /// Raw SVG image data.
+    Data(&'a str),
+}
+
+/// Creates placeholder metadata, intended as a starting point for overriding fields.
+///
+/// The [`image`](Metadata::image) is the sentinel URL `https://example.invalid/`, and every other field
+/// is empty. A defaulted `Metadata` is not valid metadata, and [`Metadata::validate`] will report it as such
+/// until at least the name and description are filled in.
+impl Default for Metadata {
+    fn default() -> Self {
+        Metadata {
+            name: String::new(),
+            description: String::new(),
+            image: Some(RawUrl::from("https://example.invalid/")),
+            image_data: None,
+            external_url: None,
+            attributes: Vec::new(),
+            background_color: None,
+            background_color_alpha: None,
+            animation_url: None,
+            youtube_url: None,
+            collection: None,
+            royalties: Vec::new(),
+            seller_fee_basis_points: None,
+            fee_recipient: None,
+            #[cfg(feature = "serde")]
+            properties: None,
+            #[cfg(feature = "serde")]
+            extra: BTreeMap::new(),
+        }
+    }
 }
 
 /// A key-value pair of attributes for an item.
+///
+/// Attributes without a `trait_type` are displayed as generic attributes.
+///
+/// Prior versions modelled each kind of value as a separate variant of this type. The value now lives in
+/// [`AttributeValue`], and the constructors such as [`AttributeEntry::string`] and [`AttributeEntry::number`]
+/// remain the recommended way to create attributes. The old type is available as the deprecated
+/// [`LegacyAttributeEntry`], which converts to and from this one.
+///
+/// When deserializing, `traitType`, `maxValue` and `displayType` are accepted as well.
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
-    serde(rename_all = "snake_case"),
-    serde(untagged)
+    serde(from = "repr::AttributeEntryRepr")
 )]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum AttributeEntry {
-    /// Textual attribute.
-    String {
-        /// Name of the trait.
-        trait_type: String,
-        /// Value of the attribute.
-        value: String,
-    },
-    /// Numerical attribute.
-    Number {
-        /// Name of the trait.
-        trait_type: String,
-        /// Value of the attribute.
-        value: u64,
-        /// How the attribute should be displayed.
-        display_type: Option<DisplayType>,
-    },
+pub struct AttributeEntry {
+    /// Name of the trait.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub trait_type: Option<String>,
+    /// Value of the attribute.
+    pub value: AttributeValue,
+    /// Maximum value of the attribute, if any.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_value: Option<i64>,
+    /// How the attribute should be displayed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub display_type: Option<DisplayType>,
+    /// Fields not otherwise recognized, such as a `rarity_score` added by rarity tools, preserved so that they
+    /// survive a round trip.
+    #[cfg(feature = "serde")]
+    #[serde(flatten)]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::json::serialize",
+            deserialize_with = "crate::borsh_with::json::deserialize"
+        )
+    )]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl AttributeEntry {
+    /// Creates an attribute with no display type.
+    pub fn new(trait_type: impl Into<String>, value: impl Into<AttributeValue>) -> Self {
+        AttributeEntry {
+            trait_type: Some(trait_type.into()),
+            value: value.into(),
+            max_value: None,
+            display_type: None,
+            #[cfg(feature = "serde")]
+            extra: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a generic attribute, which has no trait type.
+    pub fn generic(value: impl Into<AttributeValue>) -> Self {
+        AttributeEntry {
+            trait_type: None,
+            value: value.into(),
+            max_value: None,
+            display_type: None,
+            #[cfg(feature = "serde")]
+            extra: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the name of the trait, or [`None`] for generic attributes.
+    pub fn trait_type(&self) -> Option<&str> {
+        self.trait_type.as_deref()
+    }
+
+    /// Returns how the attribute is displayed, treating a numerical attribute without a
+    /// [`display_type`](AttributeEntry::display_type) as a [`Number`](DisplayType::Number), as OpenSea does.
+    ///
+    /// Textual and boolean attributes have no display type, even if one is set.
+    pub fn effective_display_type(&self) -> Option<DisplayType> {
+        match self.value {
+            AttributeValue::Integer(_) | AttributeValue::Float(_) => {
+                Some(self.display_type.clone().unwrap_or(DisplayType::Number))
+            }
+            AttributeValue::Bool(_) | AttributeValue::String(_) => None,
+        }
+    }
+
+    /// Creates a textual attribute.
+    pub fn string(trait_type: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::new(trait_type, value.into())
+    }
+
+    /// Creates a boolean attribute.
+    pub fn bool(trait_type: impl Into<String>, value: bool) -> Self {
+        Self::new(trait_type, value)
+    }
+
+    /// Creates a numerical attribute displayed as a number.
+    pub fn number(trait_type: impl Into<String>, value: i64) -> Self {
+        Self::numeric(trait_type, value, DisplayType::Number)
+    }
+
+    /// Creates a numerical attribute displayed as a number out of `max_value`.
+    pub fn number_with_max(trait_type: impl Into<String>, value: i64, max_value: i64) -> Self {
+        AttributeEntry {
+            max_value: Some(max_value),
+            ..Self::number(trait_type, value)
+        }
+    }
+
+    /// Creates a numerical attribute displayed as a boost percentage.
+    pub fn boost_percentage(trait_type: impl Into<String>, value: i64) -> Self {
+        Self::numeric(trait_type, value, DisplayType::BoostPercentage)
+    }
+
+    /// Creates a numerical attribute displayed as a boost number.
+    pub fn boost_number(trait_type: impl Into<String>, value: i64) -> Self {
+        Self::numeric(trait_type, value, DisplayType::BoostNumber)
+    }
+
+    /// Creates a numerical attribute displayed as a date, where `value` is a Unix timestamp in seconds.
+    pub fn date(trait_type: impl Into<String>, value: i64) -> Self {
+        Self::numeric(trait_type, value, DisplayType::Date)
+    }
+
+    /// Creates a fractional numerical attribute displayed as a number.
+    pub fn float(trait_type: impl Into<String>, value: f64) -> Self {
+        Self::numeric(trait_type, value, DisplayType::Number)
+    }
+
+    fn numeric(
+        trait_type: impl Into<String>,
+        value: impl Into<AttributeValue>,
+        display_type: DisplayType,
+    ) -> Self {
+        AttributeEntry {
+            display_type: Some(display_type),
+            ..Self::new(trait_type, value)
+        }
+    }
+}
+
+/// Value of an attribute.
+///
+/// When deserializing, the variant is chosen by the type of the value alone, so every value maps to exactly one
+/// variant regardless of the rest of the attribute, such as its `display_type`:
+///
+/// - booleans are read as [`Bool`](AttributeValue::Bool),
+/// - strings are read as [`String`](AttributeValue::String), even if they look like numbers,
+/// - numbers without a fraction or exponent are read as [`Integer`](AttributeValue::Integer) if they fit in an
+///   [`i64`], and
+/// - all other numbers are read as [`Float`](AttributeValue::Float).
+#[cfg_attr(
+    feature = "serde",
+    doc = "",
+    doc = "Use [`Metadata::from_str_lenient`] to read numerical strings as numbers."
+)]
+///
+/// Values are ordered by variant first, in declaration order, and then by the value itself.
+#[cfg_attr(feature = "serde", derive(Serialize), serde(untagged))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AttributeValue {
+    /// Boolean value.
+    Bool(bool),
+    /// Textual value.
+    String(String),
+    /// Integral value.
+    ///
+    /// Integers outside the range of an [`i64`] are deserialized as [`Float`](AttributeValue::Float),
+    /// which may lose precision.
+    Integer(i64),
+    /// Fractional value.
+    ///
+    /// Numbers written without a fraction or exponent are deserialized as [`Integer`](AttributeValue::Integer)
+    /// instead, while numbers such as `3.0` are kept as floats.
+    /// The value is wrapped in an [`OrderedFloat`] so that attributes remain [`Eq`] and [`Hash`].
+    Float(
+        #[cfg_attr(feature = "schemars", schemars(with = "f64"))]
+        #[cfg_attr(
+            feature = "borsh",
+            borsh(
+                serialize_with = "crate::borsh_with::ordered_float::serialize",
+                deserialize_with = "crate::borsh_with::ordered_float::deserialize"
+            )
+        )]
+        OrderedFloat<f64>,
+    ),
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        AttributeValue::String(value)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        AttributeValue::String(value.to_owned())
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        AttributeValue::Integer(value)
+    }
+}
+
+impl From<i32> for AttributeValue {
+    fn from(value: i32) -> Self {
+        AttributeValue::Integer(value.into())
+    }
+}
+
+impl From<u32> for AttributeValue {
+    fn from(value: u32) -> Self {
+        AttributeValue::Integer(value.into())
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        AttributeValue::Float(OrderedFloat(value))
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Bool(value)
+    }
+}
+
+/// Formats the value without quotes, so that `"3"` and `3` are both displayed as `3`.
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeValue::Bool(value) => write!(f, "{}", value),
+            AttributeValue::String(value) => f.write_str(value),
+            AttributeValue::Integer(value) => write!(f, "{}", value),
+            AttributeValue::Float(value) => write!(f, "{}", value),
+        }
+    }
 }
 
 /// How a numerical attribute should be displayed.
+///
+/// Display types are ordered as the variants are declared, with [`Other`](DisplayType::Other) display types
+/// last and ordered by name. This order is stable, so it can be relied upon for sorting and
+/// [`std::collections::BTreeMap`] keys.
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
-    serde(rename_all = "snake_case")
+    serde(from = "String", into = "String")
 )]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DisplayType {
     /// As a number.
     Number,
@@ -84,96 +730,40 @@ pub enum DisplayType {
     BoostNumber,
     /// As a date.
     Date,
+    /// Any display type not covered by the other variants, preserved verbatim.
+    Other(String),
 }
 
-#[cfg(feature = "serde")]
-mod rgb8_fromhex_opt {
-    use rgb::{ComponentSlice, RGB8};
-    use serde::{de::Error, Deserializer, Serialize, Serializer};
-
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<RGB8>, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        match hex::deserialize(deserializer)
-            .map::<Option<Vec<_>>, _>(Some)
-            .unwrap_or_default()
-        {
-            Some(s) => {
-                if s.len() != 3 {
-                    Err(D::Error::custom("expected color hex string"))
-                } else {
-                    Ok(Some(RGB8 {
-                        r: s[0],
-                        g: s[1],
-                        b: s[2],
-                    }))
-                }
-            }
-            None => Ok(None),
+impl DisplayType {
+    /// Returns the string form of the display type, as it appears in metadata.
+    pub fn as_str(&self) -> &str {
+        match self {
+            DisplayType::Number => "number",
+            DisplayType::BoostPercentage => "boost_percentage",
+            DisplayType::BoostNumber => "boost_number",
+            DisplayType::Date => "date",
+            DisplayType::Other(s) => s,
         }
     }
+}
 
-    pub fn serialize<S>(value: &Option<RGB8>, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        match value {
-            Some(value) => hex::serialize(value.as_slice().to_vec(), serializer),
-            None => None::<()>.serialize(serializer),
+impl From<String> for DisplayType {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "number" => DisplayType::Number,
+            "boost_percentage" => DisplayType::BoostPercentage,
+            "boost_number" => DisplayType::BoostNumber,
+            "date" => DisplayType::Date,
+            _ => DisplayType::Other(s),
         }
     }
+}
 
-    #[cfg(test)]
-    mod tests {
-        use rgb::RGB8;
-        use serde::{Deserialize, Serialize};
-
-        #[derive(Serialize, Deserialize, Debug)]
-        struct Target {
-            #[serde(with = "crate::rgb8_fromhex_opt")]
-            color: Option<RGB8>,
-        }
-
-        #[test]
-        fn from_json() {
-            let s = r#"{ "color": "f2f2f2" }"#;
-            let target: Target = serde_json::from_str(s).unwrap();
-            assert_eq!(
-                target.color,
-                Some(RGB8 {
-                    r: 242,
-                    g: 242,
-                    b: 242
-                })
-            );
-        }
-
-        #[test]
-        fn to_json() {
-            let target = Target {
-                color: Some(RGB8 {
-                    r: 242,
-                    g: 242,
-                    b: 242,
-                }),
-            };
-            let s = serde_json::to_string(&target).unwrap();
-            assert_eq!(s, r#"{"color":"f2f2f2"}"#)
-        }
-
-        #[test]
-        fn from_notcolor_json() {
-            let s = r#"{ "color": "f2f2f2f2" }"#;
-            let target = serde_json::from_str::<Target>(s);
-            assert!(target.is_err());
-        }
-
-        #[test]
-        fn from_null_json() {
-            let s = r#"{ "color": null }"#;
-            let target = serde_json::from_str::<Target>(s).unwrap();
-            assert!(target.color.is_none());
+impl From<DisplayType> for String {
+    fn from(display_type: DisplayType) -> Self {
+        match display_type {
+            DisplayType::Other(s) => s,
+            display_type => display_type.as_str().to_owned(),
         }
     }
 }
@@ -181,9 +771,11 @@ mod rgb8_fromhex_opt {
 #[cfg(feature = "serde")]
 #[cfg(test)]
 mod tests {
-    use crate::Metadata;
+    use serde_json::json;
+
+    use crate::{fixtures, AttributeEntry, AttributeValue, DisplayType, Image, Metadata, RawUrl};
 
-    const PLANETPASS_ITEM: &str = r#"
+    pub(crate) const PLANETPASS_ITEM: &str = r#"
     {
         "image": "https://assets.wanderers.ai/file/planetpass/vid/0/0.mp4",
         "description": "Visit this planet and get a free Rocketeer NFT from Alucard.eth!",
@@ -230,4 +822,771 @@ mod tests {
         let metadata = serde_json::from_str::<Metadata>(PLANETPASS_ITEM);
         assert!(metadata.is_ok());
     }
+
+    #[test]
+    pub fn attribute_accessors() {
+        let mut metadata = serde_json::from_str::<Metadata>(PLANETPASS_ITEM).unwrap();
+        assert_eq!(
+            metadata.string_value("Faction"),
+            Some("Coalition for Uncorrupted Biology")
+        );
+        assert_eq!(
+            metadata.attribute("Core"),
+            Some(&AttributeEntry::string("Core", "Vortex"))
+        );
+        assert_eq!(metadata.attribute("Level"), None);
+        assert_eq!(metadata.number_value("Core"), None);
+
+        metadata.attributes.push(AttributeEntry::number("Level", 3));
+        assert_eq!(metadata.number_value("Level"), Some(3));
+        assert_eq!(metadata.string_value("Level"), None);
+    }
+
+    #[test]
+    pub fn attributes_with_display() {
+        let mut metadata = serde_json::from_str::<Metadata>(PLANETPASS_ITEM).unwrap();
+        metadata.attributes.extend([
+            AttributeEntry::date("Discovered", 1_546_360_800),
+            AttributeEntry::number("Level", 3),
+            AttributeEntry {
+                display_type: Some(DisplayType::Date),
+                ..AttributeEntry::string("Named", "2019-01-01")
+            },
+            AttributeEntry {
+                display_type: Some(DisplayType::Date),
+                ..AttributeEntry::new("Visited", 1_546_360_800.5)
+            },
+            AttributeEntry::boost_percentage("Stamina", 10),
+        ]);
+
+        let dates = metadata
+            .attributes_with_display(DisplayType::Date)
+            .filter_map(AttributeEntry::trait_type)
+            .collect::<Vec<_>>();
+        assert_eq!(dates, ["Discovered", "Visited"]);
+        assert_eq!(
+            metadata
+                .attributes_with_display(DisplayType::Other("percentage".into()))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    pub fn attribute_trait_type() {
+        assert_eq!(
+            AttributeEntry::string("Core", "Vortex").trait_type(),
+            Some("Core")
+        );
+        assert_eq!(
+            AttributeEntry::number("Level", 3).trait_type(),
+            Some("Level")
+        );
+        assert_eq!(AttributeEntry::generic(3).trait_type(), None);
+    }
+
+    #[test]
+    pub fn effective_display_type() {
+        assert_eq!(
+            AttributeEntry::new("Level", 3).effective_display_type(),
+            Some(DisplayType::Number)
+        );
+        assert_eq!(
+            AttributeEntry::new("Stamina", 1.5).effective_display_type(),
+            Some(DisplayType::Number)
+        );
+        assert_eq!(
+            AttributeEntry::date("Discovered", 1_546_360_800).effective_display_type(),
+            Some(DisplayType::Date)
+        );
+        assert_eq!(
+            AttributeEntry::new("Core", "Vortex").effective_display_type(),
+            None
+        );
+        assert_eq!(
+            AttributeEntry {
+                display_type: Some(DisplayType::Number),
+                ..AttributeEntry::string("Level", "3")
+            }
+            .effective_display_type(),
+            None
+        );
+        assert_eq!(
+            AttributeEntry::bool("Icy", true).effective_display_type(),
+            None
+        );
+    }
+
+    #[test]
+    pub fn attribute_constructors() {
+        let cases = [
+            (
+                AttributeEntry::string("Core", "Vortex"),
+                json!({ "trait_type": "Core", "value": "Vortex" }),
+            ),
+            (
+                AttributeEntry::number("Generation", 2),
+                json!({ "display_type": "number", "trait_type": "Generation", "value": 2 }),
+            ),
+            (
+                AttributeEntry::boost_percentage("Stamina Increase", 10),
+                json!({ "display_type": "boost_percentage", "trait_type": "Stamina Increase", "value": 10 }),
+            ),
+            (
+                AttributeEntry::boost_number("Aqua Power", 40),
+                json!({ "display_type": "boost_number", "trait_type": "Aqua Power", "value": 40 }),
+            ),
+            (
+                AttributeEntry::date("Birthday", 1546360800),
+                json!({ "display_type": "date", "trait_type": "Birthday", "value": 1546360800 }),
+            ),
+        ];
+
+        for (attribute, expected) in cases {
+            assert_eq!(serde_json::to_value(&attribute).unwrap(), expected);
+            assert_eq!(
+                serde_json::from_value::<AttributeEntry>(expected).unwrap(),
+                attribute
+            );
+        }
+    }
+
+    #[test]
+    pub fn skip_none_fields() {
+        let metadata = fixtures::builder().build().unwrap();
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        let mut keys = value.as_object().unwrap().keys().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["description", "image", "name"]);
+
+        assert_eq!(serde_json::from_value::<Metadata>(value).unwrap(), metadata);
+    }
+
+    #[test]
+    pub fn only_populated_keys() {
+        let metadata = fixtures::builder()
+            .attribute(AttributeEntry::string("Core", "Vortex"))
+            .build()
+            .unwrap();
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        let mut keys = value.as_object().unwrap().keys().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["attributes", "description", "image", "name"]);
+        assert!(!serde_json::to_string(&metadata).unwrap().contains("null"));
+
+        let mut value = value;
+        for key in [
+            "external_url",
+            "background_color",
+            "animation_url",
+            "youtube_url",
+        ] {
+            value[key] = serde_json::Value::Null;
+        }
+        assert_eq!(serde_json::from_value::<Metadata>(value).unwrap(), metadata);
+    }
+
+    #[test]
+    pub fn background_color_alpha() {
+        let mut value =
+            serde_json::to_value(serde_json::from_str::<Metadata>(PLANETPASS_ITEM).unwrap())
+                .unwrap();
+        value["background_color"] = json!("#F2F2F280");
+        let metadata = serde_json::from_value::<Metadata>(value).unwrap();
+        assert_eq!(
+            metadata.background_color,
+            Some(rgb::RGB8::new(0xf2, 0xf2, 0xf2).into())
+        );
+        assert_eq!(metadata.background_color_alpha, Some(0x80));
+
+        let s = serde_json::to_string(&metadata).unwrap();
+        assert!(s.contains(r#""background_color":"f2f2f280""#));
+        assert_eq!(serde_json::from_str::<Metadata>(&s).unwrap(), metadata);
+
+        let partial = crate::PartialMetadata::from(metadata.clone());
+        assert_eq!(
+            serde_json::to_string(&partial).unwrap(),
+            serde_json::to_string(&metadata).unwrap()
+        );
+
+        let metadata = Metadata {
+            background_color_alpha: None,
+            ..metadata
+        };
+        let s = serde_json::to_string(&metadata).unwrap();
+        assert!(s.contains(r#""background_color":"f2f2f2""#));
+    }
+
+    #[test]
+    pub fn explicit_null_fields() {
+        let s = fixtures::metadata_json_with(json!({
+            "external_url": null,
+            "background_color": null,
+            "animation_url": null,
+            "youtube_url": null
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert!(metadata.external_url.is_none());
+        assert!(metadata.background_color.is_none());
+    }
+
+    #[test]
+    pub fn skip_empty_attributes() {
+        let metadata = fixtures::builder().build().unwrap();
+
+        let s = serde_json::to_string(&metadata).unwrap();
+        assert!(!s.contains("attributes"));
+        assert_eq!(serde_json::from_str::<Metadata>(&s).unwrap(), metadata);
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        let mut with_empty = value.clone();
+        with_empty["attributes"] = json!([]);
+        assert_eq!(
+            serde_json::from_value::<Metadata>(with_empty).unwrap(),
+            metadata
+        );
+    }
+
+    #[test]
+    pub fn float_attributes() {
+        let s = r#"
+        [
+            { "display_type": "boost_percentage", "trait_type": "Stamina Increase", "value": 1.4 },
+            { "display_type": "boost_number", "trait_type": "Aqua Power", "value": 40 },
+            { "trait_type": "Speed", "value": 3.0 }
+        ]
+        "#;
+        let attributes = serde_json::from_str::<Vec<AttributeEntry>>(s).unwrap();
+        assert_eq!(attributes[0].value, AttributeValue::from(1.4));
+        assert_eq!(attributes[1].value, AttributeValue::Integer(40));
+        assert_eq!(attributes[2].value, AttributeValue::from(3.0));
+
+        let values = serde_json::to_string(&attributes).unwrap();
+        assert!(values.contains(r#""value":1.4"#));
+        assert!(values.contains(r#""value":40,"#));
+        assert!(values.contains(r#""value":3.0"#));
+    }
+
+    #[test]
+    pub fn signed_attributes() {
+        for value in [i64::MIN, -40, -1, 0, i64::MAX] {
+            let attribute = json!({ "trait_type": "Temperature", "value": value });
+            let parsed = serde_json::from_value::<AttributeEntry>(attribute.clone()).unwrap();
+            assert_eq!(parsed.value, AttributeValue::Integer(value));
+            assert_eq!(serde_json::to_value(&parsed).unwrap()["value"], value);
+        }
+
+        let attribute = json!({ "trait_type": "Temperature", "value": u64::MAX });
+        let parsed = serde_json::from_value::<AttributeEntry>(attribute).unwrap();
+        assert!(matches!(parsed.value, AttributeValue::Float(_)));
+    }
+
+    #[test]
+    pub fn float_or_integer() {
+        let float = serde_json::from_str::<AttributeEntry>(r#"{"trait_type":"Speed","value":1.5}"#)
+            .unwrap();
+        assert_eq!(
+            float,
+            AttributeEntry {
+                trait_type: Some("Speed".into()),
+                value: AttributeValue::Float(1.5.into()),
+                max_value: None,
+                display_type: None,
+                extra: Default::default(),
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&float).unwrap()["value"],
+            serde_json::Value::from(1.5)
+        );
+
+        let integer =
+            serde_json::from_str::<AttributeEntry>(r#"{"trait_type":"Speed","value":3}"#).unwrap();
+        assert_eq!(integer.value, AttributeValue::Integer(3));
+    }
+
+    #[test]
+    pub fn value_precedence() {
+        let cases = [
+            (json!("Vortex"), AttributeValue::String("Vortex".into())),
+            (json!("5"), AttributeValue::String("5".into())),
+            (json!("true"), AttributeValue::String("true".into())),
+            (json!(5), AttributeValue::Integer(5)),
+            (json!(-5), AttributeValue::Integer(-5)),
+            (json!(5.0), AttributeValue::Float(5.0.into())),
+            (json!(5.5), AttributeValue::Float(5.5.into())),
+            (json!(true), AttributeValue::Bool(true)),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(
+                serde_json::from_value::<AttributeValue>(value.clone()).unwrap(),
+                expected
+            );
+            for attribute in [
+                json!({ "trait_type": "Level", "value": value }),
+                json!({ "value": value, "trait_type": "Level" }),
+                json!({ "trait_type": "Level", "value": value, "display_type": "number" }),
+                json!({ "display_type": "number", "value": value, "trait_type": "Level" }),
+                json!({ "trait_type": "Level", "value": value, "rarity_score": 1.5 }),
+                json!({ "value": value }),
+            ] {
+                let parsed = serde_json::from_value::<AttributeEntry>(attribute.clone()).unwrap();
+                assert_eq!(parsed.value, expected, "{}", attribute);
+            }
+        }
+
+        for value in [json!(null), json!([5]), json!({ "value": 5 })] {
+            let error = serde_json::from_value::<AttributeValue>(value).unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("expected a string, number or boolean"));
+        }
+    }
+
+    #[test]
+    pub fn max_value() {
+        let s = r#"{"trait_type":"Level","value":5,"max_value":10}"#;
+        let attribute = serde_json::from_str::<AttributeEntry>(s).unwrap();
+        assert_eq!(attribute.value, AttributeValue::Integer(5));
+        assert_eq!(attribute.max_value, Some(10));
+        assert_eq!(
+            serde_json::to_value(&attribute).unwrap(),
+            json!({ "trait_type": "Level", "value": 5, "max_value": 10 })
+        );
+
+        let attribute = AttributeEntry::number("Level", 5);
+        assert!(serde_json::to_value(&attribute)
+            .unwrap()
+            .get("max_value")
+            .is_none());
+    }
+
+    #[test]
+    pub fn bool_attributes() {
+        let s = r#"
+        [
+            { "trait_type": "Legendary", "value": true },
+            { "trait_type": "Cursed", "value": false },
+            { "trait_type": "Core", "value": "true" },
+            { "trait_type": "Level", "value": 1 }
+        ]
+        "#;
+        let attributes = serde_json::from_str::<Vec<AttributeEntry>>(s).unwrap();
+        assert_eq!(
+            attributes[..3],
+            [
+                AttributeEntry::bool("Legendary", true),
+                AttributeEntry::bool("Cursed", false),
+                AttributeEntry::string("Core", "true"),
+            ]
+        );
+        assert_eq!(attributes[3], AttributeEntry::new("Level", 1));
+
+        let value = serde_json::to_value(&attributes).unwrap();
+        assert_eq!(value[0]["value"], json!(true));
+        assert_eq!(value[1]["value"], json!(false));
+        assert_eq!(value[2]["value"], json!("true"));
+    }
+
+    #[test]
+    pub fn attribute_extra_fields() {
+        let s = r#"{"trait_type":"Level","value":3,"max_value":5,"display_type":"number","rarity_score":12.5}"#;
+        let attribute = serde_json::from_str::<AttributeEntry>(s).unwrap();
+        assert_eq!(attribute.value, AttributeValue::Integer(3));
+        assert_eq!(attribute.max_value, Some(5));
+        assert_eq!(attribute.display_type, Some(DisplayType::Number));
+        assert_eq!(attribute.extra["rarity_score"], json!(12.5));
+        assert_eq!(attribute.extra.len(), 1);
+
+        assert_eq!(
+            serde_json::to_value(&attribute).unwrap(),
+            serde_json::from_str::<serde_json::Value>(s).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn bool_round_trip() {
+        let s = r#"{"trait_type":"Shiny","value":true}"#;
+        let attribute = serde_json::from_str::<AttributeEntry>(s).unwrap();
+        assert_eq!(attribute.value, AttributeValue::Bool(true));
+        assert_eq!(serde_json::to_string(&attribute).unwrap(), s);
+    }
+
+    #[test]
+    pub fn max_value_round_trip() {
+        let attribute = AttributeEntry::number_with_max("Level", 3, 5);
+        let s = serde_json::to_string(&attribute).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AttributeEntry>(&s).unwrap(),
+            attribute
+        );
+
+        let s = r#"{"trait_type":"Level","value":7,"max_value":5,"display_type":"number"}"#;
+        let attribute = serde_json::from_str::<AttributeEntry>(s).unwrap();
+        assert_eq!(attribute, AttributeEntry::number_with_max("Level", 7, 5));
+    }
+
+    #[test]
+    pub fn normalize() {
+        let a = Metadata {
+            attributes: vec![
+                AttributeEntry::string("Core", "Vortex"),
+                AttributeEntry::number("Level", 5),
+                AttributeEntry::generic("Rare"),
+                AttributeEntry::string("Core", "Nebula"),
+            ],
+            ..Default::default()
+        };
+        let b = Metadata {
+            attributes: vec![
+                AttributeEntry::number("Level", 5),
+                AttributeEntry::string("Core", "Nebula"),
+                AttributeEntry::generic("Rare"),
+                AttributeEntry::string("Core", "Vortex"),
+            ],
+            ..Default::default()
+        };
+        assert_ne!(a, b);
+
+        let a = a.normalized();
+        assert_eq!(a, b.normalized());
+        assert_eq!(
+            a.attributes,
+            [
+                AttributeEntry::generic("Rare"),
+                AttributeEntry::string("Core", "Nebula"),
+                AttributeEntry::string("Core", "Vortex"),
+                AttributeEntry::number("Level", 5),
+            ]
+        );
+
+        let rarity = |rarity: &str| {
+            let mut attribute = AttributeEntry::string("Core", "Vortex");
+            attribute.extra.insert("rarity".into(), json!(rarity));
+            attribute
+        };
+        let a = Metadata {
+            attributes: vec![rarity("rare"), rarity("common")],
+            ..Default::default()
+        };
+        let b = Metadata {
+            attributes: vec![rarity("common"), rarity("rare")],
+            ..Default::default()
+        };
+        assert_eq!(a.normalized(), b.clone().normalized());
+        assert_eq!(
+            b.normalized().attributes,
+            [rarity("common"), rarity("rare")]
+        );
+    }
+
+    #[test]
+    pub fn generic_attributes() {
+        let s = r#"
+        [
+            { "trait_type": "Core", "value": "Vortex" },
+            { "value": "Happy" },
+            { "value": 3 }
+        ]
+        "#;
+        let attributes = serde_json::from_str::<Vec<AttributeEntry>>(s).unwrap();
+        assert_eq!(attributes[0], AttributeEntry::string("Core", "Vortex"));
+        assert_eq!(attributes[1], AttributeEntry::generic("Happy"));
+        assert_eq!(attributes[2], AttributeEntry::generic(3));
+
+        let value = serde_json::to_value(&attributes).unwrap();
+        assert_eq!(value[1], json!({ "value": "Happy" }));
+        assert!(value[2].get("trait_type").is_none());
+    }
+
+    #[test]
+    pub fn unknown_display_type() {
+        let s = r#"{"trait_type":"Luck","value":40,"display_type":"percentage"}"#;
+        let attribute = serde_json::from_str::<AttributeEntry>(s).unwrap();
+        assert_eq!(
+            attribute.display_type,
+            Some(DisplayType::Other("percentage".into()))
+        );
+        assert_eq!(serde_json::to_string(&attribute).unwrap(), s);
+
+        let s = r#"{"trait_type":"Luck","value":40,"display_type":"boost_number"}"#;
+        let attribute = serde_json::from_str::<AttributeEntry>(s).unwrap();
+        assert_eq!(attribute.display_type, Some(DisplayType::BoostNumber));
+    }
+
+    #[test]
+    pub fn string_display_type() {
+        let s = r#"{"trait_type":"Birthday","display_type":"date","value":"2021-06-01"}"#;
+        let attribute = serde_json::from_str::<AttributeEntry>(s).unwrap();
+        assert_eq!(
+            attribute,
+            AttributeEntry {
+                display_type: Some(DisplayType::Date),
+                ..AttributeEntry::string("Birthday", "2021-06-01")
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&attribute).unwrap(),
+            serde_json::from_str::<serde_json::Value>(s).unwrap()
+        );
+
+        let s = r#"{"trait_type":"Birthday","display_type":"date","value":1622505600}"#;
+        let attribute = serde_json::from_str::<AttributeEntry>(s).unwrap();
+        assert_eq!(attribute, AttributeEntry::date("Birthday", 1622505600));
+    }
+
+    #[test]
+    pub fn attribute_values() {
+        let cases = [
+            (
+                AttributeEntry::generic("Happy"),
+                json!({ "value": "Happy" }),
+            ),
+            (
+                AttributeEntry::string("Core", "Vortex"),
+                json!({ "trait_type": "Core", "value": "Vortex" }),
+            ),
+            (
+                AttributeEntry::new("Level", -3),
+                json!({ "trait_type": "Level", "value": -3 }),
+            ),
+            (
+                AttributeEntry::number_with_max("Level", 3, 5),
+                json!({ "trait_type": "Level", "value": 3, "max_value": 5, "display_type": "number" }),
+            ),
+            (
+                AttributeEntry::float("Speed", 1.5),
+                json!({ "trait_type": "Speed", "value": 1.5, "display_type": "number" }),
+            ),
+            (
+                AttributeEntry::bool("Legendary", true),
+                json!({ "trait_type": "Legendary", "value": true }),
+            ),
+        ];
+
+        for (attribute, expected) in cases {
+            assert_eq!(serde_json::to_value(&attribute).unwrap(), expected);
+            assert_eq!(
+                serde_json::from_value::<AttributeEntry>(expected).unwrap(),
+                attribute
+            );
+        }
+    }
+
+    #[test]
+    pub fn extra_fields() {
+        let s = fixtures::metadata_json_with(json!({
+            "edition": 42
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(metadata.extra["edition"], json!(42));
+
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&s).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn unknown_fields_round_trip() {
+        let s = fixtures::metadata_json_with(json!({
+            "dna": "a7f3c91e",
+            "edition": 7,
+            "compiler": { "name": "HashLips Art Engine", "version": "1.1.2" }
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(
+            metadata.extra.keys().collect::<Vec<_>>(),
+            ["compiler", "dna", "edition"]
+        );
+        assert!(!metadata.extra.contains_key("name"));
+
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&s).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn properties() {
+        let s = fixtures::metadata_json_with(json!({
+            "properties": {
+                "generation": 2,
+                "dna": "0x5f3759df",
+                "parents": [{ "id": 1 }, { "id": 2, "tags": ["a", "b"] }],
+                "rich_property": {
+                    "name": "Name",
+                    "value": "123",
+                    "css": { "font-weight": "bold" }
+                }
+            }
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        let properties = metadata.properties.as_ref().unwrap();
+        assert_eq!(properties["generation"], json!(2));
+        assert_eq!(properties.get_str("dna"), Some("0x5f3759df"));
+        assert_eq!(
+            properties.get_array("parents").unwrap()[1]["tags"][1],
+            json!("b")
+        );
+        assert_eq!(
+            properties["rich_property"]["css"]["font-weight"],
+            json!("bold")
+        );
+        assert!(metadata.extra.is_empty());
+
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&s).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn image_data() {
+        let s = r#"
+        {
+            "name": "Bag #1",
+            "description": "Loot is randomized adventurer gear generated and stored on chain.",
+            "image_data": "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 350 350\"><text x=\"10\" y=\"20\">Grim Shout</text></svg>"
+        }
+        "#;
+        let metadata = serde_json::from_str::<Metadata>(s).unwrap();
+        assert_eq!(metadata.image, None);
+        assert!(matches!(
+            metadata.primary_image(),
+            Some(Image::Data(svg)) if svg.starts_with("<svg")
+        ));
+        assert_eq!(metadata.validate(), Ok(()));
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        assert!(value.get("image").is_none());
+        assert_eq!(value, serde_json::from_str::<serde_json::Value>(s).unwrap());
+
+        let metadata = Metadata {
+            image: Some(RawUrl::from("https://example.com/1.png")),
+            ..metadata
+        };
+        assert!(matches!(
+            metadata.primary_image(),
+            Some(Image::Url(url)) if url.as_str() == "https://example.com/1.png"
+        ));
+    }
+
+    #[test]
+    pub fn image_and_image_data() {
+        let s = r#"
+        {
+            "name": "Bag #1",
+            "description": "Loot is randomized adventurer gear generated and stored on chain.",
+            "image": "https://example.com/1.png",
+            "image_data": "<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>"
+        }
+        "#;
+        let metadata = serde_json::from_str::<Metadata>(s).unwrap();
+        assert!(matches!(metadata.primary_image(), Some(Image::Url(_))));
+        assert_eq!(
+            metadata.image_data.as_deref(),
+            Some(r#"<svg xmlns="http://www.w3.org/2000/svg"></svg>"#)
+        );
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::from_str::<serde_json::Value>(s).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn canonical_key_order() {
+        let metadata = fixtures::builder()
+            .external_url("https://wanderers.ai/")
+            .attribute(AttributeEntry::string("Core", "Vortex"))
+            .background_color(rgb::RGB8::new(0xf2, 0xf2, 0xf2))
+            .animation_url(fixtures::IMAGE)
+            .youtube_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_string(&metadata).unwrap(),
+            format!(
+                concat!(
+                    r#"{{"name":"{name}","description":"{description}","image":"{image}","#,
+                    r#""external_url":"https://wanderers.ai/","#,
+                    r#""attributes":[{{"trait_type":"Core","value":"Vortex"}}],"#,
+                    r#""background_color":"f2f2f2","#,
+                    r#""animation_url":"{image}","#,
+                    r#""youtube_url":"https://www.youtube.com/watch?v=dQw4w9WgXcQ"}}"#,
+                ),
+                name = fixtures::NAME,
+                description = fixtures::DESCRIPTION,
+                image = fixtures::IMAGE,
+            )
+        );
+    }
+
+    #[test]
+    pub fn default_key_order() {
+        let mut metadata = fixtures::builder()
+            .image_data("<svg></svg>")
+            .collection(crate::CollectionInfo::new("Planetpass"))
+            .royalty(crate::Royalty::new(
+                "0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6",
+                250,
+            ))
+            .seller_fee_basis_points(250)
+            .fee_recipient("0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6")
+            .properties(crate::Properties::from(
+                json!({ "generation": 2 }).as_object().unwrap().clone(),
+            ))
+            .build()
+            .unwrap();
+        metadata.extra.insert("tags".into(), json!(["art"]));
+        metadata.extra.insert("edition".into(), json!(1));
+        assert_eq!(
+            serde_json::to_string(&metadata).unwrap(),
+            format!(
+                concat!(
+                    r#"{{"name":"{}","description":"{}","image":"{}","#,
+                    r#""image_data":"<svg></svg>","#,
+                    r#""collection":{{"name":"Planetpass"}},"#,
+                    r#""royalties":[{{"account":"0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6","value":250}}],"#,
+                    r#""seller_fee_basis_points":250,"#,
+                    r#""fee_recipient":"0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6","#,
+                    r#""properties":{{"generation":2}},"#,
+                    r#""edition":1,"tags":["art"]}}"#,
+                ),
+                fixtures::NAME,
+                fixtures::DESCRIPTION,
+                fixtures::IMAGE,
+            )
+        );
+    }
+
+    #[test]
+    fn display_type_order() {
+        let mut display_types = vec![
+            DisplayType::Other("percentage".into()),
+            DisplayType::Date,
+            DisplayType::Other("level".into()),
+            DisplayType::BoostNumber,
+            DisplayType::BoostPercentage,
+            DisplayType::Number,
+        ];
+        display_types.sort();
+        assert_eq!(
+            display_types,
+            [
+                DisplayType::Number,
+                DisplayType::BoostPercentage,
+                DisplayType::BoostNumber,
+                DisplayType::Date,
+                DisplayType::Other("level".into()),
+                DisplayType::Other("percentage".into()),
+            ]
+        );
+    }
 }
@@ -0,0 +1,134 @@
+use crate::Metadata;
+
+/// File extensions of animations and their MIME types.
+const ANIMATION_TYPES: &[(&str, &str)] = &[
+    ("mp4", "video/mp4"),
+    ("m4v", "video/mp4"),
+    ("webm", "video/webm"),
+    ("mov", "video/quicktime"),
+    ("ogv", "video/ogg"),
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("oga", "audio/ogg"),
+    ("ogg", "audio/ogg"),
+    ("flac", "audio/flac"),
+    ("glb", "model/gltf-binary"),
+    ("gltf", "model/gltf+json"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+];
+
+/// Returns the extension of the last path segment of `url`, ignoring any query or fragment.
+pub(crate) fn extension(url: &str) -> Option<&str> {
+    if url.starts_with("data:") {
+        return None;
+    }
+    let url = url.split(['?', '#']).next()?;
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url,
+    };
+    let name = path.rsplit('/').next()?;
+    match name.rsplit_once('.')? {
+        ("", _) | (_, "") => None,
+        (_, extension) => Some(extension),
+    }
+}
+
+impl Metadata {
+    /// Returns the lowercase file extension of the [`image`](Metadata::image), such as `"png"`, ignoring any
+    /// query or fragment.
+    ///
+    /// This is useful for spotting images that are actually videos, such as `"mp4"`.
+    pub fn image_extension(&self) -> Option<String> {
+        extension(self.image.as_ref()?).map(str::to_ascii_lowercase)
+    }
+
+    /// Infers the MIME type of the [`animation_url`](Metadata::animation_url) from its file extension, such as
+    /// `video/mp4` for `.mp4` or `model/gltf-binary` for `.glb`.
+    ///
+    /// Returns `None` if there is no animation or its extension is not recognized.
+    pub fn animation_mime(&self) -> Option<&'static str> {
+        let extension = extension(self.animation_url.as_ref()?)?;
+        ANIMATION_TYPES
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(extension))
+            .map(|&(_, mime)| mime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Metadata, RawUrl};
+
+    fn animation(url: &str) -> Metadata {
+        Metadata {
+            animation_url: Some(RawUrl::from(url)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn image_extension() {
+        let image = |url: &str| Metadata {
+            image: Some(RawUrl::from(url)),
+            ..Default::default()
+        };
+        assert_eq!(
+            image("https://assets.wanderers.ai/file/planetpass/vid/0/0.mp4")
+                .image_extension()
+                .as_deref(),
+            Some("mp4")
+        );
+        assert_eq!(
+            image("https://example.com/x.png?w=1")
+                .image_extension()
+                .as_deref(),
+            Some("png")
+        );
+        assert_eq!(
+            image("https://example.com/logo.SVG#icon")
+                .image_extension()
+                .as_deref(),
+            Some("svg")
+        );
+        assert_eq!(image("https://example.com/image").image_extension(), None);
+        assert_eq!(
+            image("data:image/png;base64,iVBORw0KGgo=").image_extension(),
+            None
+        );
+    }
+
+    #[test]
+    fn animation_mime() {
+        assert_eq!(
+            animation("https://assets.wanderers.ai/file/planetpass/vid/0/0.mp4").animation_mime(),
+            Some("video/mp4")
+        );
+        assert_eq!(
+            animation("ipfs://QmWS1VAdMD353A6SDk9wNyvkT14kyCiZrNDYAad4w1tKqT/model.GLB")
+                .animation_mime(),
+            Some("model/gltf-binary")
+        );
+        assert_eq!(
+            animation("https://example.com/index.html?id=1#top").animation_mime(),
+            Some("text/html")
+        );
+        assert_eq!(
+            animation("songs/1.mp3").animation_mime(),
+            Some("audio/mpeg")
+        );
+
+        assert_eq!(
+            animation("https://example.com/1.xyz").animation_mime(),
+            None
+        );
+        assert_eq!(
+            animation("https://example.com/v1.0/track").animation_mime(),
+            None
+        );
+        assert_eq!(animation("https://example.com/.mp4").animation_mime(), None);
+        assert_eq!(animation("https://example.html").animation_mime(), None);
+        assert_eq!(Metadata::default().animation_mime(), None);
+    }
+}
@@ -0,0 +1,260 @@
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{contract::MAX_BASIS_POINTS, AttributeEntry, BuilderError, Metadata, MetadataBuilder};
+
+/// Off-chain metadata for a Solana token, following the
+/// [Metaplex Token Metadata standard](https://docs.metaplex.com/programs/token-metadata/token-standard).
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetaplexMetadata {
+    /// Name of the asset.
+    pub name: String,
+    /// Symbol of the collection the asset belongs to.
+    #[serde(default)]
+    pub symbol: String,
+    /// Human-readable description of the asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Royalty paid to the creators on secondary sales, in basis points, where 100 is a 1% fee.
+    #[serde(default)]
+    pub seller_fee_basis_points: u16,
+    /// URL to image of the asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub image: Option<Url>,
+    /// URL to a multi-media attachment for the asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub animation_url: Option<Url>,
+    /// External URL to another site.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub external_url: Option<Url>,
+    /// Attributes of the asset.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeEntry>,
+    /// Files and creators of the asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<MetaplexProperties>,
+}
+
+/// The `properties` object of [`MetaplexMetadata`].
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetaplexProperties {
+    /// Files associated with the asset.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<MetaplexFile>,
+    /// Category of the asset, such as `"image"`, `"video"` or `"audio"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Creators of the asset and their share of royalties.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub creators: Vec<Creator>,
+}
+
+/// A file associated with a Metaplex asset.
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetaplexFile {
+    /// Location of the file.
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::url::serialize",
+            deserialize_with = "crate::borsh_with::url::deserialize"
+        )
+    )]
+    pub uri: Url,
+    /// MIME type of the file, such as `"image/png"`.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Whether the file is served from a CDN.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cdn: bool,
+}
+
+/// A creator of a Metaplex asset.
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Creator {
+    /// Address of the creator's account.
+    pub address: String,
+    /// Percentage of royalties paid to the creator.
+    pub share: u8,
+}
+
+/// Converts OpenSea metadata into Metaplex metadata.
+///
+/// [`Metadata::image_data`], [`Metadata::background_color`], [`Metadata::youtube_url`],
+/// [`Metadata::collection`], [`Metadata::royalties`], [`Metadata::properties`] and [`Metadata::extra`]
+/// are dropped, as are relative URLs, so call [`Metadata::resolve_urls`] first. The symbol is left empty, and
+/// [`Metadata::fee_recipient`] is dropped since Metaplex pays royalties to the creators.
+///
+/// [`Metadata::seller_fee_basis_points`] above 10000, which is more than the whole sale, is clamped to 10000.
+impl From<Metadata> for MetaplexMetadata {
+    fn from(metadata: Metadata) -> Self {
+        MetaplexMetadata {
+            name: metadata.name,
+            symbol: String::new(),
+            description: Some(metadata.description),
+            seller_fee_basis_points: metadata
+                .seller_fee_basis_points
+                .map_or(0, |basis_points| basis_points.min(MAX_BASIS_POINTS) as u16),
+            image: metadata.image.and_then(|image| image.parse().ok()),
+            animation_url: metadata
+                .animation_url
+                .and_then(|animation_url| animation_url.parse().ok()),
+            external_url: metadata.external_url,
+            attributes: metadata.attributes,
+            properties: None,
+        }
+    }
+}
+
+/// Converts Metaplex metadata into OpenSea metadata, failing if a field required by [`Metadata`] is missing.
+///
+/// A non-zero [`seller_fee_basis_points`](MetaplexMetadata::seller_fee_basis_points) is carried across, while
+/// [`symbol`](MetaplexMetadata::symbol) and [`properties`](MetaplexMetadata::properties) are dropped.
+impl TryFrom<MetaplexMetadata> for Metadata {
+    type Error = BuilderError;
+
+    fn try_from(metadata: MetaplexMetadata) -> Result<Self, Self::Error> {
+        let mut builder = MetadataBuilder::new()
+            .name(metadata.name)
+            .attributes(metadata.attributes);
+        if let Some(description) = metadata.description {
+            builder = builder.description(description);
+        }
+        if let Some(image) = metadata.image {
+            builder = builder.image(image);
+        }
+        if let Some(animation_url) = metadata.animation_url {
+            builder = builder.animation_url(animation_url);
+        }
+        if let Some(external_url) = metadata.external_url {
+            builder = builder.external_url(external_url);
+        }
+        if metadata.seller_fee_basis_points != 0 {
+            builder = builder.seller_fee_basis_points(metadata.seller_fee_basis_points.into());
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AttributeEntry, BuilderError, Metadata, MetaplexMetadata};
+
+    const SOLFLARE_X: &str = r#"
+    {
+        "name": "Solflare X NFT",
+        "symbol": "",
+        "description": "Celebratory Solflare NFT for the Solflare X launch",
+        "seller_fee_basis_points": 0,
+        "image": "https://www.arweave.net/abcd5678?ext=png",
+        "animation_url": "https://www.arweave.net/efgh1234?ext=mp4",
+        "external_url": "https://solflare.com/",
+        "attributes": [
+            { "trait_type": "web", "value": "yes" },
+            { "trait_type": "mobile", "value": "yes" },
+            { "trait_type": "extension", "value": "yes" }
+        ],
+        "properties": {
+            "files": [
+                { "uri": "https://www.arweave.net/abcd5678?ext=png", "type": "image/png" },
+                { "uri": "https://watch.videodelivery.net/9876jkl", "type": "unknown", "cdn": true },
+                { "uri": "https://www.arweave.net/efgh1234?ext=mp4", "type": "video/mp4" }
+            ],
+            "category": "video",
+            "creators": [
+                { "address": "xEtQ9Fpv62qdc1GYfpNReMasVTe9YW5bHJwfVKqo72u", "share": 100 }
+            ]
+        }
+    }
+    "#;
+
+    #[test]
+    fn solflare_example() {
+        let metadata = serde_json::from_str::<MetaplexMetadata>(SOLFLARE_X).unwrap();
+        let properties = metadata.properties.as_ref().unwrap();
+        assert_eq!(properties.files.len(), 3);
+        assert!(properties.files[1].cdn);
+        assert_eq!(properties.files[0].mime_type.as_deref(), Some("image/png"));
+        assert_eq!(properties.creators[0].share, 100);
+
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::from_str::<serde_json::Value>(SOLFLARE_X).unwrap()
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let metadata = serde_json::from_str::<MetaplexMetadata>(SOLFLARE_X).unwrap();
+        let metadata = Metadata::try_from(metadata).unwrap();
+        assert_eq!(metadata.name, "Solflare X NFT");
+        assert_eq!(metadata.attributes[0], AttributeEntry::string("web", "yes"));
+        assert_eq!(
+            metadata.external_url.as_ref().map(|url| url.as_str()),
+            Some("https://solflare.com/")
+        );
+
+        let metadata = MetaplexMetadata::from(metadata);
+        assert_eq!(metadata.symbol, "");
+        assert_eq!(metadata.attributes.len(), 3);
+        assert!(metadata.properties.is_none());
+
+        for (basis_points, expected) in [(None, 0), (Some(250), 250), (Some(70_000), 10_000)] {
+            let metadata = Metadata {
+                seller_fee_basis_points: basis_points,
+                ..Metadata::default()
+            };
+            assert_eq!(
+                MetaplexMetadata::from(metadata).seller_fee_basis_points,
+                expected
+            );
+        }
+
+        let metadata = MetaplexMetadata {
+            description: None,
+            ..metadata
+        };
+        assert_eq!(
+            Metadata::try_from(metadata),
+            Err(BuilderError::MissingField("description"))
+        );
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh_round_trip() {
+        let metadata = serde_json::from_str::<MetaplexMetadata>(SOLFLARE_X).unwrap();
+        let bytes = borsh::to_vec(&metadata).unwrap();
+        assert_eq!(
+            borsh::from_slice::<MetaplexMetadata>(&bytes).unwrap(),
+            metadata
+        );
+    }
+}
@@ -0,0 +1,521 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use futures_util::future::join_all;
+use reqwest::{header, Client, RequestBuilder, Response, StatusCode};
+use tokio::sync::Semaphore;
+use url::Url;
+
+use crate::{ipfs_to_gateway, DataUri, Metadata};
+
+/// Media types accepted for animations by default, where a trailing `/*` matches any subtype.
+const ANIMATION_TYPES: &[&str] = &[
+    "video/*",
+    "audio/*",
+    "model/gltf-binary",
+    "model/gltf+json",
+    "text/html",
+];
+
+/// Checks that the URLs in metadata resolve and serve the right kind of content, with
+/// [`validate`](NetworkValidator::validate).
+///
+/// A new validator allows 16 concurrent requests, each with a timeout of 10 seconds, and has no IPFS gateway.
+/// Clones share the same concurrency limit, so one validator can be cloned across many tasks, such as one per
+/// token of a collection.
+#[derive(Debug, Clone)]
+pub struct NetworkValidator {
+    client: Client,
+    permits: Arc<Semaphore>,
+    timeout: Duration,
+    ipfs_gateway: Option<Url>,
+    animation_types: Vec<String>,
+}
+
+impl NetworkValidator {
+    /// Creates a validator with the default limits.
+    pub fn new() -> Self {
+        NetworkValidator {
+            client: Client::new(),
+            permits: Arc::new(Semaphore::new(16)),
+            timeout: Duration::from_secs(10),
+            ipfs_gateway: None,
+            animation_types: ANIMATION_TYPES
+                .iter()
+                .map(|&media_type| media_type.into())
+                .collect(),
+        }
+    }
+
+    /// Sets the maximum number of requests in flight at once, which is at least 1.
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        self.permits = Arc::new(Semaphore::new(limit.max(1)));
+        self
+    }
+
+    /// Sets how long each request may take, including following redirects.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the HTTP gateway that `ipfs://` URLs are fetched through, such as `https://ipfs.io/`. See
+    /// [`ipfs_to_gateway`].
+    ///
+    /// Without a gateway, IPFS URLs are reported as [`UnsupportedScheme`](NetworkError::UnsupportedScheme).
+    pub fn ipfs_gateway(mut self, gateway: Url) -> Self {
+        self.ipfs_gateway = Some(gateway);
+        self
+    }
+
+    /// Replaces the media types accepted for the animation, such as `"video/mp4"`, where `"video/*"` accepts
+    /// any video.
+    pub fn animation_types(
+        mut self,
+        media_types: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.animation_types = media_types
+            .into_iter()
+            .map(|media_type| media_type.into().to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Fetches [`image`](Metadata::image), [`animation_url`](Metadata::animation_url) and
+    /// [`external_url`](Metadata::external_url), returning a report for each field that is present.
+    ///
+    /// The image must be served as `image/*` and the animation as one of the
+    /// [`animation_types`](NetworkValidator::animation_types), while the external URL only has to resolve.
+    /// `data:` URIs are decoded rather than fetched.
+    pub async fn validate(&self, metadata: &Metadata) -> Vec<UrlReport> {
+        let urls = [
+            ("image", metadata.image.as_deref()),
+            ("animation_url", metadata.animation_url.as_deref()),
+            (
+                "external_url",
+                metadata.external_url.as_ref().map(Url::as_str),
+            ),
+        ];
+        join_all(urls.into_iter().filter_map(|(field, url)| {
+            let url = url?;
+            Some(async move {
+                UrlReport {
+                    field,
+                    url: url.to_owned(),
+                    result: self.check(field, url).await,
+                }
+            })
+        }))
+        .await
+    }
+
+    /// Fetches a single URL and checks its media type against what `field` expects.
+    async fn check(&self, field: &str, url: &str) -> Result<Option<String>, NetworkError> {
+        let media_type = if url.starts_with("data:") {
+            Some(
+                DataUri::parse(url)
+                    .ok_or(NetworkError::InvalidDataUri)?
+                    .mime,
+            )
+        } else {
+            self.fetch(self.resolve(url)?).await?
+        };
+
+        match (field, media_type) {
+            ("external_url", media_type) => Ok(media_type),
+            (_, None) => Err(NetworkError::MissingContentType),
+            (_, Some(media_type)) if self.accepts(field, &media_type) => Ok(Some(media_type)),
+            (_, Some(media_type)) => Err(NetworkError::UnexpectedContentType(media_type)),
+        }
+    }
+
+    /// Checks that `media_type` is allowed for `field`.
+    fn accepts(&self, field: &str, media_type: &str) -> bool {
+        if field == "image" {
+            return media_type.starts_with("image/");
+        }
+        self.animation_types
+            .iter()
+            .any(|allowed| match allowed.strip_suffix("/*") {
+                Some(kind) => media_type
+                    .strip_prefix(kind)
+                    .is_some_and(|subtype| subtype.starts_with('/')),
+                None => media_type == allowed,
+            })
+    }
+
+    /// Returns the HTTP URL to fetch `url` from, going through the IPFS gateway if there is one.
+    fn resolve(&self, url: &str) -> Result<Url, NetworkError> {
+        if let Some(gateway_url) = self
+            .ipfs_gateway
+            .as_ref()
+            .and_then(|gateway| ipfs_to_gateway(url, gateway))
+        {
+            return gateway_url.map_err(|_| NetworkError::InvalidUrl);
+        }
+
+        let url = Url::parse(url).map_err(|error| match error {
+            url::ParseError::RelativeUrlWithoutBase => NetworkError::Relative,
+            _ => NetworkError::InvalidUrl,
+        })?;
+        match url.scheme() {
+            "http" | "https" => Ok(url),
+            scheme => Err(NetworkError::UnsupportedScheme(scheme.to_owned())),
+        }
+    }
+
+    /// Requests `url` with `HEAD`, falling back to a ranged `GET` for servers that do not support `HEAD`, and
+    /// returns the media type it is served as.
+    async fn fetch(&self, url: Url) -> Result<Option<String>, NetworkError> {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("the semaphore is never closed");
+
+        let mut response = self.send(self.client.head(url.clone())).await?;
+        if matches!(
+            response.status(),
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+        ) {
+            response = self
+                .send(self.client.get(url).header(header::RANGE, "bytes=0-0"))
+                .await?;
+        }
+        if !response.status().is_success() {
+            return Err(NetworkError::Status(response.status().as_u16()));
+        }
+
+        Ok(response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|media_type| media_type.trim().to_ascii_lowercase())
+            .filter(|media_type| !media_type.is_empty()))
+    }
+
+    /// Sends a request with the timeout applied.
+    async fn send(&self, request: RequestBuilder) -> Result<Response, NetworkError> {
+        request.timeout(self.timeout).send().await.map_err(|error| {
+            if error.is_timeout() {
+                NetworkError::Timeout
+            } else {
+                NetworkError::Request(error.to_string())
+            }
+        })
+    }
+}
+
+impl Default for NetworkValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The outcome of fetching one URL of the metadata, as returned by [`NetworkValidator::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlReport {
+    /// Name of the field, such as `"image"`.
+    pub field: &'static str,
+    /// The URL as it appears in the metadata.
+    pub url: String,
+    /// The media type the URL is served as, such as `"image/png"`, or why it failed.
+    ///
+    /// Only the external URL may be served without a media type.
+    pub result: Result<Option<String>, NetworkError>,
+}
+
+impl UrlReport {
+    /// Checks that the URL resolved and served the right kind of content.
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+impl fmt::Display for UrlReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.result {
+            Ok(Some(media_type)) => write!(f, "{} `{}`: {}", self.field, self.url, media_type),
+            Ok(None) => write!(f, "{} `{}`: ok", self.field, self.url),
+            Err(error) => write!(f, "{} `{}`: {}", self.field, self.url, error),
+        }
+    }
+}
+
+/// Why a URL failed a [`NetworkValidator`] check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    /// The URL is relative, so cannot be fetched until [resolved](Metadata::resolve_urls).
+    Relative,
+    /// The URL could not be parsed.
+    InvalidUrl,
+    /// The URL has a scheme that cannot be fetched, such as `ipfs` without a gateway.
+    UnsupportedScheme(String),
+    /// A `data:` URI could not be decoded.
+    InvalidDataUri,
+    /// The request timed out.
+    Timeout,
+    /// The request failed before a response was received, such as when the host could not be reached.
+    Request(String),
+    /// The server responded with an unsuccessful HTTP status.
+    Status(u16),
+    /// The response had no `Content-Type`.
+    MissingContentType,
+    /// The response had a media type that the field does not allow.
+    UnexpectedContentType(String),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::Relative => write!(f, "relative URLs cannot be fetched"),
+            NetworkError::InvalidUrl => write!(f, "the URL is invalid"),
+            NetworkError::UnsupportedScheme(scheme) => {
+                write!(f, "`{}` URLs cannot be fetched", scheme)
+            }
+            NetworkError::InvalidDataUri => write!(f, "the data URI could not be decoded"),
+            NetworkError::Timeout => write!(f, "the request timed out"),
+            NetworkError::Request(error) => write!(f, "the request failed: {}", error),
+            NetworkError::Status(status) => write!(f, "the server responded with HTTP {}", status),
+            NetworkError::MissingContentType => write!(f, "the response has no content type"),
+            NetworkError::UnexpectedContentType(media_type) => {
+                write!(f, "unexpected content type `{}`", media_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+    use url::Url;
+
+    use crate::{Metadata, NetworkError, NetworkValidator, RawUrl, UrlReport};
+
+    const CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    /// Requests currently being handled by the mock server, and the most handled at once.
+    #[derive(Default)]
+    struct InFlight {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    /// Starts a mock HTTP server, returning its base URL.
+    async fn server(in_flight: Arc<InFlight>) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(respond(stream, in_flight.clone()));
+            }
+        });
+        base
+    }
+
+    async fn respond(mut stream: TcpStream, in_flight: Arc<InFlight>) {
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            match stream.read(&mut buf).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => request.extend_from_slice(&buf[..n]),
+            }
+        }
+        let request = String::from_utf8_lossy(&request);
+        let mut line = request.split_whitespace();
+        let (method, path) = (line.next().unwrap(), line.next().unwrap());
+
+        let current = in_flight.current.fetch_add(1, Ordering::SeqCst) + 1;
+        in_flight.max.fetch_max(current, Ordering::SeqCst);
+        let (status, content_type) = match (method, path) {
+            (_, "/image.png") => ("200 OK", Some("image/png")),
+            (_, "/page.html") => ("200 OK", Some("text/html; charset=utf-8")),
+            ("HEAD", "/video.mp4") => ("405 Method Not Allowed", None),
+            ("GET", "/video.mp4") => ("206 Partial Content", Some("video/mp4")),
+            (_, "/untyped") => ("200 OK", None),
+            (_, "/slow.png") => {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                ("200 OK", Some("image/png"))
+            }
+            (_, "/hang.png") => {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                ("200 OK", Some("image/png"))
+            }
+            (_, path) if path == format!("/ipfs/{}/0.png", CID) => ("200 OK", Some("image/png")),
+            _ => ("404 Not Found", Some("text/plain")),
+        };
+        in_flight.current.fetch_sub(1, Ordering::SeqCst);
+
+        let content_type = content_type
+            .map(|content_type| format!("Content-Type: {}\r\n", content_type))
+            .unwrap_or_default();
+        let response = format!(
+            "HTTP/1.1 {}\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n",
+            status, content_type
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+    }
+
+    fn metadata(base: &Url, image: &str) -> Metadata {
+        Metadata {
+            image: Some(RawUrl::from(base.join(image).unwrap())),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_image() {
+        let base = server(Arc::default()).await;
+        let mut metadata = metadata(&base, "missing.png");
+        metadata.animation_url = Some(RawUrl::from(base.join("video.mp4").unwrap()));
+        metadata.external_url = Some(base.join("page.html").unwrap());
+
+        let reports = NetworkValidator::new().validate(&metadata).await;
+        assert_eq!(
+            reports,
+            [
+                UrlReport {
+                    field: "image",
+                    url: base.join("missing.png").unwrap().into(),
+                    result: Err(NetworkError::Status(404)),
+                },
+                UrlReport {
+                    field: "animation_url",
+                    url: base.join("video.mp4").unwrap().into(),
+                    result: Ok(Some("video/mp4".into())),
+                },
+                UrlReport {
+                    field: "external_url",
+                    url: base.join("page.html").unwrap().into(),
+                    result: Ok(Some("text/html".into())),
+                },
+            ]
+        );
+        assert!(!reports[0].is_ok());
+        assert!(reports[0]
+            .to_string()
+            .ends_with("the server responded with HTTP 404"));
+    }
+
+    #[tokio::test]
+    async fn content_types() {
+        let base = server(Arc::default()).await;
+        let mut metadata = metadata(&base, "page.html");
+        metadata.animation_url = Some(RawUrl::from(base.join("image.png").unwrap()));
+        metadata.external_url = Some(base.join("untyped").unwrap());
+
+        let results = NetworkValidator::new()
+            .validate(&metadata)
+            .await
+            .into_iter()
+            .map(|report| report.result)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            [
+                Err(NetworkError::UnexpectedContentType("text/html".into())),
+                Err(NetworkError::UnexpectedContentType("image/png".into())),
+                Ok(None),
+            ]
+        );
+
+        metadata.animation_url = Some(RawUrl::from(base.join("image.png").unwrap()));
+        let reports = NetworkValidator::new()
+            .animation_types(["image/*"])
+            .validate(&metadata)
+            .await;
+        assert_eq!(reports[1].result, Ok(Some("image/png".into())));
+
+        metadata.image = Some(RawUrl::from(base.join("untyped").unwrap()));
+        let reports = NetworkValidator::new().validate(&metadata).await;
+        assert_eq!(reports[0].result, Err(NetworkError::MissingContentType));
+    }
+
+    #[tokio::test]
+    async fn ipfs_gateway() {
+        let base = server(Arc::default()).await;
+        let metadata = Metadata {
+            image: Some(RawUrl(format!("ipfs://{}/0.png", CID))),
+            ..Default::default()
+        };
+
+        let reports = NetworkValidator::new().validate(&metadata).await;
+        assert_eq!(
+            reports[0].result,
+            Err(NetworkError::UnsupportedScheme("ipfs".into()))
+        );
+
+        let reports = NetworkValidator::new()
+            .ipfs_gateway(base)
+            .validate(&metadata)
+            .await;
+        assert_eq!(reports[0].result, Ok(Some("image/png".into())));
+    }
+
+    #[tokio::test]
+    async fn without_requests() {
+        let validator = NetworkValidator::new();
+        let metadata = Metadata {
+            image: Some(RawUrl::from("data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=")),
+            animation_url: Some(RawUrl::from("0.mp4")),
+            ..Default::default()
+        };
+        let results = validator
+            .validate(&metadata)
+            .await
+            .into_iter()
+            .map(|report| report.result)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            [
+                Ok(Some("image/svg+xml".into())),
+                Err(NetworkError::Relative)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn timeout() {
+        let base = server(Arc::default()).await;
+        let reports = NetworkValidator::new()
+            .timeout(Duration::from_millis(100))
+            .validate(&metadata(&base, "hang.png"))
+            .await;
+        assert_eq!(reports[0].result, Err(NetworkError::Timeout));
+    }
+
+    #[tokio::test]
+    async fn concurrency() {
+        let in_flight = Arc::<InFlight>::default();
+        let base = server(in_flight.clone()).await;
+        let validator = NetworkValidator::new().concurrency(2);
+        let metadata = metadata(&base, "slow.png");
+
+        let tasks = (0..8)
+            .map(|_| {
+                let (validator, metadata) = (validator.clone(), metadata.clone());
+                tokio::spawn(async move { validator.validate(&metadata).await })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            assert!(task.await.unwrap()[0].is_ok());
+        }
+        assert!(in_flight.max.load(Ordering::SeqCst) <= 2);
+    }
+}
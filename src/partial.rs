@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::Deserialize;
+use url::Url;
+
+use crate::{
+    AttributeEntry, BackgroundColor, BuilderError, CollectionInfo, Metadata, Properties, RawUrl,
+    Royalty,
+};
+
+/// [`Metadata`] in which every field is optional, for ingesting documents that lack required fields.
+///
+/// Deserializing accepts the same alternative spellings as [`Metadata`], but never fails because a field is
+/// missing. Convert into [`Metadata`] with [`TryFrom`], or backfill the missing fields with
+/// [`into_metadata_with_defaults`](PartialMetadata::into_metadata_with_defaults).
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[serde(from = "crate::repr::MetadataRepr")]
+pub struct PartialMetadata {
+    /// Name of the item. See [`Metadata::name`].
+    pub name: Option<String>,
+    /// Human-readable description of the item. See [`Metadata::description`].
+    pub description: Option<String>,
+    /// URL to image of the item. See [`Metadata::image`].
+    pub image: Option<RawUrl>,
+    /// Raw SVG image data of the item. See [`Metadata::image_data`].
+    pub image_data: Option<String>,
+    /// External URL to another site. See [`Metadata::external_url`].
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub external_url: Option<Url>,
+    /// Attributes for the item. See [`Metadata::attributes`].
+    pub attributes: Vec<AttributeEntry>,
+    /// Background color of the item. See [`Metadata::background_color`].
+    pub background_color: Option<BackgroundColor>,
+    /// Alpha channel of the background color. See [`Metadata::background_color_alpha`].
+    pub background_color_alpha: Option<u8>,
+    /// URL to multi-media attachment for the item. See [`Metadata::animation_url`].
+    pub animation_url: Option<RawUrl>,
+    /// URL to a YouTube video. See [`Metadata::youtube_url`].
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::option_url::serialize",
+            deserialize_with = "crate::borsh_with::option_url::deserialize"
+        )
+    )]
+    pub youtube_url: Option<Url>,
+    /// Collection the item belongs to. See [`Metadata::collection`].
+    pub collection: Option<CollectionInfo>,
+    /// Royalties paid on secondary sales. See [`Metadata::royalties`].
+    pub royalties: Vec<Royalty>,
+    /// Royalty hint for marketplaces, in basis points. See [`Metadata::seller_fee_basis_points`].
+    pub seller_fee_basis_points: Option<u32>,
+    /// Address that the royalty is paid to. See [`Metadata::fee_recipient`].
+    pub fee_recipient: Option<String>,
+    /// Free-form ERC-1155 properties of the item. See [`Metadata::properties`].
+    pub properties: Option<Properties>,
+    /// Fields not otherwise recognized. See [`Metadata::extra`].
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::json::serialize",
+            deserialize_with = "crate::borsh_with::json::deserialize"
+        )
+    )]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl PartialMetadata {
+    /// Converts into [`Metadata`], using `image` if neither an image URL nor image data is present and
+    /// `description` if no description is present.
+    ///
+    /// Fails only if the name is missing.
+    pub fn into_metadata_with_defaults(
+        mut self,
+        image: impl Into<RawUrl>,
+        description: impl Into<String>,
+    ) -> Result<Metadata, Vec<BuilderError>> {
+        if self.image.is_none() && self.image_data.is_none() {
+            self.image = Some(image.into());
+        }
+        self.description.get_or_insert_with(|| description.into());
+        Metadata::try_from(self)
+    }
+}
+
+/// Converts partial metadata into [`Metadata`], failing with a [`BuilderError::MissingField`] for each required
+/// field that is missing, in the order `name`, `description` and `image`.
+impl TryFrom<PartialMetadata> for Metadata {
+    type Error = Vec<BuilderError>;
+
+    fn try_from(partial: PartialMetadata) -> Result<Self, Self::Error> {
+        let mut errors = Vec::new();
+        if partial.name.is_none() {
+            errors.push(BuilderError::MissingField("name"));
+        }
+        if partial.description.is_none() {
+            errors.push(BuilderError::MissingField("description"));
+        }
+        if partial.image.is_none() && partial.image_data.is_none() {
+            errors.push(BuilderError::MissingField("image"));
+        }
+        let (name, description) = match (partial.name, partial.description) {
+            (Some(name), Some(description)) if errors.is_empty() => (name, description),
+            _ => return Err(errors),
+        };
+
+        Ok(Metadata {
+            name,
+            description,
+            image: partial.image,
+            image_data: partial.image_data,
+            external_url: partial.external_url,
+            attributes: partial.attributes,
+            background_color: partial.background_color,
+            background_color_alpha: partial.background_color_alpha,
+            animation_url: partial.animation_url,
+            youtube_url: partial.youtube_url,
+            collection: partial.collection,
+            royalties: partial.royalties,
+            seller_fee_basis_points: partial.seller_fee_basis_points,
+            fee_recipient: partial.fee_recipient,
+            properties: partial.properties,
+            extra: partial.extra,
+        })
+    }
+}
+
+/// Converts metadata into partial metadata, which cannot fail.
+impl From<Metadata> for PartialMetadata {
+    fn from(metadata: Metadata) -> Self {
+        PartialMetadata {
+            name: Some(metadata.name),
+            description: Some(metadata.description),
+            image: metadata.image,
+            image_data: metadata.image_data,
+            external_url: metadata.external_url,
+            attributes: metadata.attributes,
+            background_color: metadata.background_color,
+            background_color_alpha: metadata.background_color_alpha,
+            animation_url: metadata.animation_url,
+            youtube_url: metadata.youtube_url,
+            collection: metadata.collection,
+            royalties: metadata.royalties,
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            fee_recipient: metadata.fee_recipient,
+            properties: metadata.properties,
+            extra: metadata.extra,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, BuilderError, Metadata, PartialMetadata, RawUrl};
+
+    #[test]
+    fn name_only() {
+        let value = serde_json::json!({ "name": fixtures::NAME });
+        let partial = serde_json::from_value::<PartialMetadata>(value.clone()).unwrap();
+        assert_eq!(partial.name.as_deref(), Some(fixtures::NAME));
+        assert_eq!(partial.description, None);
+        assert_eq!(serde_json::to_value(&partial).unwrap(), value);
+
+        assert_eq!(
+            Metadata::try_from(partial.clone()),
+            Err(vec![
+                BuilderError::MissingField("description"),
+                BuilderError::MissingField("image"),
+            ])
+        );
+
+        let metadata = partial
+            .into_metadata_with_defaults(fixtures::IMAGE, "")
+            .unwrap();
+        assert_eq!(metadata.name, fixtures::NAME);
+        assert_eq!(metadata.description, "");
+        assert_eq!(metadata.image, Some(RawUrl::from(fixtures::IMAGE)));
+    }
+
+    #[test]
+    fn empty() {
+        let partial = serde_json::from_str::<PartialMetadata>("{}").unwrap();
+        assert_eq!(partial, PartialMetadata::default());
+        assert_eq!(
+            Metadata::try_from(partial.clone()),
+            Err(vec![
+                BuilderError::MissingField("name"),
+                BuilderError::MissingField("description"),
+                BuilderError::MissingField("image"),
+            ])
+        );
+        assert_eq!(
+            partial.into_metadata_with_defaults("https://example.com/0.png", ""),
+            Err(vec![BuilderError::MissingField("name")])
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let metadata = serde_json::from_str::<Metadata>(crate::tests::PLANETPASS_ITEM).unwrap();
+        let partial = PartialMetadata::from(metadata.clone());
+        assert_eq!(
+            serde_json::to_value(&partial).unwrap(),
+            serde_json::to_value(&metadata).unwrap()
+        );
+        assert_eq!(Metadata::try_from(partial), Ok(metadata));
+    }
+}
@@ -0,0 +1,215 @@
+use std::ops::RangeInclusive;
+
+use crate::{AttributeEntry, DisplayType, Metadata, ValidationError};
+
+/// File extensions of the animation formats OpenSea displays.
+const OPENSEA_ANIMATIONS: &[&str] = &[
+    "gltf", "glb", "webm", "mp4", "m4v", "ogv", "ogg", "mp3", "wav", "oga", "html",
+];
+
+/// A set of marketplace rules to check metadata against with [`Metadata::validate_for`].
+///
+/// The built-in profiles approximate each marketplace's documented behavior, which changes over time. Use
+/// [`Custom`](ValidationProfile::Custom) for anything else.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationProfile {
+    /// [OpenSea](https://docs.opensea.io/docs/metadata-standards), which truncates long names.
+    OpenSea,
+    /// [Rarible](https://docs.rarible.org/), which only displays video, audio and 3D animations.
+    Rarible,
+    /// LooksRare, which ignores `youtube_url` and display types other than numbers.
+    LooksRare,
+    /// Caller-supplied limits.
+    Custom(ProfileLimits),
+}
+
+impl ValidationProfile {
+    /// Returns the limits applied by the profile.
+    pub fn limits(&self) -> ProfileLimits {
+        let formats =
+            |formats: &[&str]| Some(formats.iter().map(|&format| format.into()).collect());
+        match self {
+            ValidationProfile::OpenSea => ProfileLimits {
+                max_name_len: Some(255),
+                animation_formats: formats(OPENSEA_ANIMATIONS),
+                display_types: Some(vec![
+                    DisplayType::Number,
+                    DisplayType::BoostNumber,
+                    DisplayType::BoostPercentage,
+                    DisplayType::Date,
+                ]),
+                ..ProfileLimits::default()
+            },
+            ValidationProfile::Rarible => ProfileLimits {
+                max_name_len: Some(100),
+                max_description_len: Some(1000),
+                schemes: ["http", "https", "ipfs", "ar"].map(String::from).to_vec(),
+                animation_formats: formats(&["mp4", "webm", "mp3", "wav", "ogg", "glb", "gltf"]),
+                ..ProfileLimits::default()
+            },
+            ValidationProfile::LooksRare => ProfileLimits {
+                schemes: ["https", "ipfs"].map(String::from).to_vec(),
+                animation_formats: formats(&["mp4", "webm", "mp3", "wav"]),
+                display_types: Some(vec![DisplayType::Number]),
+                youtube_url: false,
+                ..ProfileLimits::default()
+            },
+            ValidationProfile::Custom(limits) => limits.clone(),
+        }
+    }
+}
+
+/// Limits checked by [`Metadata::validate_for`], on top of those of [`Metadata::validate`].
+///
+/// The default imposes no limits beyond [`Metadata::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileLimits {
+    /// Maximum length of the name, in characters.
+    pub max_name_len: Option<usize>,
+    /// Maximum length of the description, in characters.
+    pub max_description_len: Option<usize>,
+    /// URL schemes allowed for the image and animation, such as `"https"`.
+    pub schemes: Vec<String>,
+    /// File extensions allowed for the animation, such as `"mp4"`, or `None` to allow any.
+    ///
+    /// Animations without a recognizable extension are not checked.
+    pub animation_formats: Option<Vec<String>>,
+    /// Display types allowed on attributes, or `None` to allow any.
+    pub display_types: Option<Vec<DisplayType>>,
+    /// Whether `youtube_url` is supported.
+    pub youtube_url: bool,
+    /// Plausible [`Date`](DisplayType::Date) values, as Unix timestamps in seconds.
+    pub dates: RangeInclusive<i64>,
+    /// Whether trait types differing only in case, such as `Background` and `background`, are duplicates.
+    pub case_insensitive_trait_types: bool,
+}
+
+impl Default for ProfileLimits {
+    fn default() -> Self {
+        ProfileLimits {
+            max_name_len: None,
+            max_description_len: None,
+            schemes: ["http", "https", "ipfs", "ar", "data"]
+                .map(String::from)
+                .to_vec(),
+            animation_formats: None,
+            display_types: None,
+            youtube_url: true,
+            dates: AttributeEntry::PLAUSIBLE_DATES,
+            case_insensitive_trait_types: false,
+        }
+    }
+}
+
+impl Metadata {
+    /// Checks the metadata as [`validate`](Metadata::validate) does, along with the limits of a marketplace
+    /// `profile`, returning every problem found.
+    pub fn validate_for(&self, profile: &ValidationProfile) -> Result<(), Vec<ValidationError>> {
+        self.validate_with(&profile.limits())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::{
+        AttributeEntry, DisplayType, Metadata, ProfileLimits, RawUrl, ValidationError,
+        ValidationProfile,
+    };
+
+    fn metadata() -> Metadata {
+        Metadata {
+            attributes: vec![
+                AttributeEntry::string("Core", "Vortex"),
+                AttributeEntry::date("Discovered", 1_546_360_800),
+            ],
+            ..crate::fixtures::metadata()
+        }
+    }
+
+    #[test]
+    fn default_matches_validate() {
+        let metadata = metadata();
+        assert_eq!(
+            metadata.validate_for(&ValidationProfile::Custom(ProfileLimits::default())),
+            metadata.validate()
+        );
+    }
+
+    #[test]
+    fn opensea_but_not_looksrare() {
+        let mut metadata = metadata();
+        metadata.animation_url = Some(RawUrl::from(
+            "https://assets.wanderers.ai/file/planetpass/glb/0.glb",
+        ));
+        metadata.youtube_url =
+            Some(Url::parse("https://www.youtube.com/watch?v=dQw4w9WgXcQ").unwrap());
+
+        assert_eq!(metadata.validate_for(&ValidationProfile::OpenSea), Ok(()));
+        assert_eq!(
+            metadata.validate_for(&ValidationProfile::LooksRare),
+            Err(vec![
+                ValidationError::UnsupportedAnimationFormat("glb".into()),
+                ValidationError::UnsupportedField("youtube_url"),
+                ValidationError::UnsupportedDisplayType {
+                    trait_type: Some("Discovered".into()),
+                    display_type: DisplayType::Date,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn lengths_and_schemes() {
+        let mut metadata = metadata();
+        metadata.name = "Rocketeer ".repeat(11);
+        metadata.image = Some(RawUrl::from("data:image/svg+xml;base64,PHN2Zy8+"));
+
+        assert_eq!(metadata.validate_for(&ValidationProfile::OpenSea), Ok(()));
+        let errors = metadata
+            .validate_for(&ValidationProfile::Rarible)
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                ValidationError::TooLong {
+                    field: "name",
+                    len: 110,
+                    max: 100,
+                },
+                ValidationError::UnsupportedScheme {
+                    field: "image",
+                    url: "data:image/svg+xml;base64,PHN2Zy8+".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "name is 110 characters long, exceeding 100"
+        );
+    }
+
+    #[test]
+    fn custom() {
+        let limits = ProfileLimits {
+            max_description_len: Some(16),
+            display_types: Some(vec![]),
+            ..ProfileLimits::default()
+        };
+        assert_eq!(
+            metadata().validate_for(&ValidationProfile::Custom(limits)),
+            Err(vec![
+                ValidationError::TooLong {
+                    field: "description",
+                    len: 64,
+                    max: 16,
+                },
+                ValidationError::UnsupportedDisplayType {
+                    trait_type: Some("Discovered".into()),
+                    display_type: DisplayType::Date,
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::ops::{Deref, DerefMut};
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A free-form `properties` object, kept verbatim.
+///
+/// Dereferences to the underlying [`Map`], with typed getters for the common cases.
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[serde(transparent)]
+pub struct Properties(
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_with::json::serialize",
+            deserialize_with = "crate::borsh_with::json::deserialize"
+        )
+    )]
+    pub Map<String, Value>,
+);
+
+impl Properties {
+    /// Returns the property with the given key, if it is a string.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.0.get(key)?.as_str()
+    }
+
+    /// Returns the property with the given key, if it is an integer that fits in an [`i64`].
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.0.get(key)?.as_i64()
+    }
+
+    /// Returns the property with the given key, if it is a number.
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.0.get(key)?.as_f64()
+    }
+
+    /// Returns the property with the given key, if it is a boolean.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.0.get(key)?.as_bool()
+    }
+
+    /// Returns the property with the given key, if it is an array.
+    pub fn get_array(&self, key: &str) -> Option<&[Value]> {
+        self.0.get(key)?.as_array().map(Vec::as_slice)
+    }
+
+    /// Returns the property with the given key, if it is an object.
+    pub fn get_object(&self, key: &str) -> Option<&Map<String, Value>> {
+        self.0.get(key)?.as_object()
+    }
+}
+
+impl Deref for Properties {
+    type Target = Map<String, Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Properties {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Map<String, Value>> for Properties {
+    fn from(properties: Map<String, Value>) -> Self {
+        Properties(properties)
+    }
+}
+
+impl From<Properties> for Map<String, Value> {
+    fn from(properties: Properties) -> Self {
+        properties.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::Properties;
+
+    #[test]
+    fn getters() {
+        let properties = serde_json::from_value::<Properties>(json!({
+            "generation": 2,
+            "dna": "0x5f3759df",
+            "weight": 1.5,
+            "legendary": false,
+            "parents": [1, 2],
+            "origin": { "planet": "Vortex", "coordinates": [0.5, -1.25] }
+        }))
+        .unwrap();
+        assert_eq!(properties.get_i64("generation"), Some(2));
+        assert_eq!(properties.get_str("dna"), Some("0x5f3759df"));
+        assert_eq!(properties.get_f64("weight"), Some(1.5));
+        assert_eq!(properties.get_bool("legendary"), Some(false));
+        assert_eq!(
+            properties.get_array("parents"),
+            Some(&[json!(1), json!(2)][..])
+        );
+        assert_eq!(
+            properties.get_object("origin").unwrap()["planet"],
+            json!("Vortex")
+        );
+        assert_eq!(properties.get_str("generation"), None);
+        assert_eq!(properties.get_i64("missing"), None);
+    }
+}
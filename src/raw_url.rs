@@ -0,0 +1,162 @@
+use std::{fmt, ops::Deref};
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// A URL kept as the original string, which may be relative to the location of the metadata.
+///
+/// Deserializing never fails on a present string, so unusual values such as `"0.png"` survive. Use
+/// [`parse`](RawUrl::parse) to obtain a [`Url`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RawUrl(pub String);
+
+impl RawUrl {
+    /// Returns the URL as written.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Parses the URL, failing with [`url::ParseError::RelativeUrlWithoutBase`] if it is relative.
+    pub fn parse(&self) -> Result<Url, url::ParseError> {
+        Url::parse(&self.0)
+    }
+
+    /// Returns `true` if the URL is relative, and so must be joined against a base before use.
+    pub fn is_relative(&self) -> bool {
+        self.parse() == Err(url::ParseError::RelativeUrlWithoutBase)
+    }
+
+    /// Joins the URL against `base`, returning absolute URLs unchanged.
+    pub fn resolve(&self, base: &Url) -> Result<Url, url::ParseError> {
+        match self.parse() {
+            Err(url::ParseError::RelativeUrlWithoutBase) => base.join(&self.0),
+            result => result,
+        }
+    }
+}
+
+impl Deref for RawUrl {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for RawUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Url> for RawUrl {
+    fn from(url: Url) -> Self {
+        RawUrl(url.into())
+    }
+}
+
+impl From<String> for RawUrl {
+    fn from(url: String) -> Self {
+        RawUrl(url)
+    }
+}
+
+impl From<&str> for RawUrl {
+    fn from(url: &str) -> Self {
+        RawUrl(url.to_owned())
+    }
+}
+
+impl TryFrom<RawUrl> for Url {
+    type Error = url::ParseError;
+
+    fn try_from(url: RawUrl) -> Result<Self, Self::Error> {
+        url.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::{Metadata, RawUrl};
+
+    #[test]
+    fn relative() {
+        let url = RawUrl::from("0.png");
+        assert!(url.is_relative());
+        assert_eq!(url.parse(), Err(url::ParseError::RelativeUrlWithoutBase));
+
+        let base =
+            Url::parse("ipfs://QmWS1VAdMD353A6SDk9wNyvkT14kyCiZrNDYAad4w1tKqT/0.json").unwrap();
+        assert_eq!(
+            url.resolve(&base).unwrap().as_str(),
+            "ipfs://QmWS1VAdMD353A6SDk9wNyvkT14kyCiZrNDYAad4w1tKqT/0.png"
+        );
+    }
+
+    #[test]
+    fn resolve_urls() {
+        let mut metadata = Metadata {
+            image: Some(RawUrl::from("images/0.png")),
+            animation_url: Some(RawUrl::from(crate::fixtures::IMAGE)),
+            ..crate::fixtures::metadata()
+        };
+        let base = Url::parse("https://example.com/metadata/0.json").unwrap();
+        metadata.resolve_urls(&base).unwrap();
+
+        assert_eq!(
+            metadata.image,
+            Some(RawUrl::from("https://example.com/metadata/images/0.png"))
+        );
+        assert_eq!(
+            metadata.animation_url,
+            Some(RawUrl::from(crate::fixtures::IMAGE))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_relative() {
+        let metadata = serde_json::from_str::<Metadata>(
+            r#"{ "name": "Bag #1", "description": "Loot", "image": "0.png" }"#,
+        )
+        .unwrap();
+        assert_eq!(metadata.image, Some(RawUrl::from("0.png")));
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap()["image"],
+            serde_json::json!("0.png")
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_data_uri() {
+        let image = "data:image/svg+xml;utf8,<svg xmlns='http://www.w3.org/2000/svg'><text>Bag #1</text></svg>";
+        let metadata = serde_json::from_value::<Metadata>(serde_json::json!({
+            "name": "Bag #1",
+            "description": "Loot",
+            "image": image,
+            "animation_url": "../animations/1.mp4"
+        }))
+        .unwrap();
+        assert_eq!(metadata.image.as_deref(), Some(image));
+        assert_ne!(
+            metadata.image.as_ref().unwrap().parse().unwrap().as_str(),
+            image
+        );
+        assert!(metadata.animation_url.as_ref().unwrap().is_relative());
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(value["image"], image);
+        assert_eq!(value["animation_url"], "../animations/1.mp4");
+    }
+}
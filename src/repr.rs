@@ -0,0 +1,593 @@
+use std::{collections::BTreeMap, fmt};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+use serde::{
+    de::{value::StringDeserializer, IntoDeserializer, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use url::Url;
+
+use crate::{
+    AttributeEntry, AttributeValue, BackgroundColor, BuilderError, CollectionInfo, ColorError,
+    DisplayType, Metadata, PartialMetadata, Properties, RawUrl, Royalty,
+};
+
+/// The shape of [`Metadata`] as accepted when deserializing, including alternative spellings of fields.
+///
+/// Converting into [`PartialMetadata`] resolves the alternatives, preferring the canonical spelling, and
+/// converting into [`Metadata`] additionally checks that the required fields are present.
+#[cfg_attr(
+    feature = "schemars",
+    derive(JsonSchema),
+    schemars(extend(
+        "required" = ["name", "description"],
+        "anyOf" = [
+            { "required": ["image"] },
+            { "required": ["image_url"] },
+            { "required": ["image_data"] },
+            { "required": ["imageData"] },
+        ],
+    ))
+)]
+#[derive(Deserialize)]
+pub(crate) struct MetadataRepr {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    image: Option<RawUrl>,
+    #[serde(default)]
+    image_url: Option<RawUrl>,
+    #[serde(default)]
+    image_data: Option<String>,
+    #[serde(rename = "imageData", default)]
+    image_data_camel: Option<String>,
+    #[serde(deserialize_with = "empty_as_none", default)]
+    external_url: Option<Url>,
+    #[serde(rename = "externalUrl", deserialize_with = "empty_as_none", default)]
+    external_url_camel: Option<Url>,
+    #[serde(deserialize_with = "empty_as_none", default)]
+    external_link: Option<Url>,
+    #[serde(deserialize_with = "deserialize_attributes", default)]
+    #[cfg_attr(feature = "schemars", schemars(schema_with = "attributes_schema"))]
+    attributes: Vec<AttributeEntry>,
+    #[serde(deserialize_with = "deserialize_attributes", default)]
+    #[cfg_attr(feature = "schemars", schemars(schema_with = "attributes_schema"))]
+    traits: Vec<AttributeEntry>,
+    #[serde(default)]
+    background_color: Option<BackgroundColorRepr>,
+    #[serde(rename = "backgroundColor", default)]
+    background_color_camel: Option<BackgroundColorRepr>,
+    #[serde(deserialize_with = "empty_as_none", default)]
+    animation_url: Option<RawUrl>,
+    #[serde(rename = "animationUrl", deserialize_with = "empty_as_none", default)]
+    animation_url_camel: Option<RawUrl>,
+    #[serde(deserialize_with = "empty_as_none", default)]
+    youtube_url: Option<Url>,
+    #[serde(rename = "youtubeUrl", deserialize_with = "empty_as_none", default)]
+    youtube_url_camel: Option<Url>,
+    #[serde(default)]
+    collection: Option<CollectionInfo>,
+    #[serde(default)]
+    royalties: Vec<Royalty>,
+    #[serde(default)]
+    seller_fee_basis_points: Option<u32>,
+    #[serde(rename = "sellerFeeBasisPoints", default)]
+    seller_fee_basis_points_camel: Option<u32>,
+    #[serde(default)]
+    fee_recipient: Option<String>,
+    #[serde(rename = "feeRecipient", default)]
+    fee_recipient_camel: Option<String>,
+    #[serde(default)]
+    properties: Option<Properties>,
+    #[serde(flatten)]
+    extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl From<MetadataRepr> for PartialMetadata {
+    fn from(repr: MetadataRepr) -> Self {
+        let background_color = repr.background_color.or(repr.background_color_camel);
+        PartialMetadata {
+            name: repr.name,
+            description: repr.description,
+            image: repr.image.or(repr.image_url),
+            image_data: repr.image_data.or(repr.image_data_camel),
+            external_url: repr
+                .external_url
+                .or(repr.external_url_camel)
+                .or(repr.external_link),
+            attributes: merge_traits(repr.attributes, repr.traits),
+            background_color: background_color.map(|color| color.0),
+            background_color_alpha: background_color.and_then(|color| color.1),
+            animation_url: repr.animation_url.or(repr.animation_url_camel),
+            youtube_url: repr.youtube_url.or(repr.youtube_url_camel),
+            collection: repr.collection,
+            royalties: repr.royalties,
+            seller_fee_basis_points: repr
+                .seller_fee_basis_points
+                .or(repr.seller_fee_basis_points_camel),
+            fee_recipient: repr.fee_recipient.or(repr.fee_recipient_camel),
+            properties: repr.properties,
+            extra: repr.extra,
+        }
+    }
+}
+
+/// Appends the entries of `traits` that are not already among `attributes`, so that documents repeating the
+/// same array under both keys are not duplicated.
+fn merge_traits(
+    mut attributes: Vec<AttributeEntry>,
+    traits: Vec<AttributeEntry>,
+) -> Vec<AttributeEntry> {
+    for attribute in traits {
+        if !attributes.contains(&attribute) {
+            attributes.push(attribute);
+        }
+    }
+    attributes
+}
+
+impl TryFrom<MetadataRepr> for Metadata {
+    type Error = BuilderError;
+
+    fn try_from(repr: MetadataRepr) -> Result<Self, Self::Error> {
+        Metadata::try_from(PartialMetadata::from(repr)).map_err(|mut errors| errors.swap_remove(0))
+    }
+}
+
+/// A [`BackgroundColor`] and the alpha channel of an 8-digit color, as accepted when deserializing. See
+/// [`BackgroundColor::parse_with_alpha`].
+#[cfg_attr(
+    feature = "schemars",
+    derive(JsonSchema),
+    schemars(extend(
+        "pattern" = r"^\s*(#?([0-9a-fA-F]{3}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})|rgb\(\s*\d{1,3}\s*,\s*\d{1,3}\s*,\s*\d{1,3}\s*\)|[a-zA-Z]+)\s*$"
+    ))
+)]
+#[derive(Deserialize, Clone, Copy)]
+#[serde(try_from = "String")]
+pub(crate) struct BackgroundColorRepr(BackgroundColor, Option<u8>);
+
+impl TryFrom<String> for BackgroundColorRepr {
+    type Error = ColorError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let (color, alpha) = BackgroundColor::parse_with_alpha(&s)?;
+        Ok(BackgroundColorRepr(color, alpha))
+    }
+}
+
+/// The shape of [`AttributeEntry`] as accepted when deserializing, including camelCase spellings of fields.
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Deserialize)]
+pub(crate) struct AttributeEntryRepr {
+    #[serde(default)]
+    trait_type: Option<String>,
+    #[serde(rename = "traitType", default)]
+    trait_type_camel: Option<String>,
+    value: AttributeValue,
+    #[serde(default)]
+    max_value: Option<i64>,
+    #[serde(rename = "maxValue", default)]
+    max_value_camel: Option<i64>,
+    #[serde(default)]
+    display_type: Option<DisplayType>,
+    #[serde(rename = "displayType", default)]
+    display_type_camel: Option<DisplayType>,
+    #[serde(flatten)]
+    extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl From<AttributeEntryRepr> for AttributeEntry {
+    fn from(repr: AttributeEntryRepr) -> Self {
+        AttributeEntry {
+            trait_type: repr.trait_type.or(repr.trait_type_camel),
+            value: repr.value,
+            max_value: repr.max_value.or(repr.max_value_camel),
+            display_type: repr.display_type.or(repr.display_type_camel),
+            extra: repr.extra,
+        }
+    }
+}
+
+/// Picks the variant from the type of the value, rather than trying each variant in turn as `untagged` would.
+impl<'de> Deserialize<'de> for AttributeValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct AttributeValueVisitor;
+
+        impl<'de> Visitor<'de> for AttributeValueVisitor {
+            type Value = AttributeValue;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string, number or boolean")
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(AttributeValue::Bool(value))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(AttributeValue::Integer(value))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(i64::try_from(value)
+                    .map(AttributeValue::Integer)
+                    .unwrap_or(AttributeValue::Float((value as f64).into())))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(AttributeValue::Float(value.into()))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(AttributeValue::String(value.to_owned()))
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+                Ok(AttributeValue::String(value))
+            }
+        }
+
+        deserializer.deserialize_any(AttributeValueVisitor)
+    }
+}
+
+/// Deserializes an optional URL, treating an empty string as absent.
+fn empty_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(s) if !s.is_empty() => {
+            let deserializer: StringDeserializer<D::Error> = s.into_deserializer();
+            T::deserialize(deserializer).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Deserializes attributes from either an array of attribute objects or an object mapping each trait type to
+/// its value, such as `{ "Core": "Vortex", "Level": 3 }`. `null` is treated as no attributes.
+fn deserialize_attributes<'de, D>(deserializer: D) -> Result<Vec<AttributeEntry>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct AttributesVisitor;
+
+    impl<'de> Visitor<'de> for AttributesVisitor {
+        type Value = Vec<AttributeEntry>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array of attributes or an object of trait types to values")
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E> {
+            Ok(Vec::new())
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut attributes = Vec::new();
+            while let Some(attribute) = seq.next_element()? {
+                attributes.push(attribute);
+            }
+            Ok(attributes)
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut attributes = Vec::new();
+            while let Some((trait_type, value)) = map.next_entry::<String, AttributeValue>()? {
+                attributes.push(AttributeEntry::new(trait_type, value));
+            }
+            Ok(attributes)
+        }
+    }
+
+    deserializer.deserialize_any(AttributesVisitor)
+}
+
+/// Describes the forms accepted by [`deserialize_attributes`].
+#[cfg(feature = "schemars")]
+fn attributes_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    let array = generator.subschema_for::<Vec<AttributeEntry>>();
+    let map = generator.subschema_for::<BTreeMap<String, AttributeValue>>();
+    schemars::json_schema!({ "anyOf": [array, map, { "type": "null" }] })
+}
+
+#[cfg(test)]
+mod tests {
+    use rgb::RGB8;
+    use serde_json::json;
+
+    use crate::{fixtures, AttributeEntry, DisplayType, Metadata, RawUrl};
+
+    #[test]
+    fn image_url() {
+        let value = json!({
+            "name": fixtures::NAME,
+            "description": fixtures::DESCRIPTION,
+            "image_url": fixtures::IMAGE
+        });
+        let metadata = serde_json::from_value::<Metadata>(value).unwrap();
+        assert_eq!(metadata.image, Some(RawUrl::from(fixtures::IMAGE)));
+        assert!(metadata.extra.is_empty());
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        assert!(value.get("image_url").is_none());
+        assert_eq!(value["image"], fixtures::IMAGE);
+    }
+
+    #[test]
+    fn image_and_image_url() {
+        let value = fixtures::metadata_json_with(json!({
+            "image_url": "https://example.com/old.png",
+            "image": "https://example.com/new.png"
+        }));
+        let metadata = serde_json::from_value::<Metadata>(value).unwrap();
+        assert_eq!(
+            metadata.image,
+            Some(RawUrl::from("https://example.com/new.png"))
+        );
+    }
+
+    #[test]
+    fn missing_image() {
+        let value = json!({ "name": fixtures::NAME, "description": fixtures::DESCRIPTION });
+        let error = serde_json::from_value::<Metadata>(value).unwrap_err();
+        assert!(error.to_string().contains("missing required field `image`"));
+    }
+
+    #[test]
+    fn external_link() {
+        let s = fixtures::metadata_json_with(json!({
+            "external_link": "https://wanderers.ai/"
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(
+            metadata.external_url.as_ref().map(|url| url.as_str()),
+            Some("https://wanderers.ai/")
+        );
+        assert!(metadata.extra.is_empty());
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        assert!(value.get("external_link").is_none());
+        assert_eq!(value["external_url"], "https://wanderers.ai/");
+        assert_eq!(serde_json::from_value::<Metadata>(value).unwrap(), metadata);
+    }
+
+    #[test]
+    fn external_url_and_external_link() {
+        let s = fixtures::metadata_json_with(json!({
+            "external_link": "https://example.com/link",
+            "external_url": "https://example.com/url"
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(
+            metadata.external_url.as_ref().map(|url| url.as_str()),
+            Some("https://example.com/url")
+        );
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(serde_json::from_value::<Metadata>(value).unwrap(), metadata);
+    }
+
+    #[test]
+    fn camel_case() {
+        let s = fixtures::metadata_json_with(json!({
+            "imageData": "<svg></svg>",
+            "externalUrl": "https://wanderers.ai/",
+            "backgroundColor": "ffffff",
+            "animationUrl": fixtures::IMAGE,
+            "youtubeUrl": "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "sellerFeeBasisPoints": 250,
+            "feeRecipient": "0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6",
+            "attributes": [
+                { "traitType": "Level", "value": 5, "maxValue": 10, "displayType": "number" }
+            ]
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(metadata.image_data.as_deref(), Some("<svg></svg>"));
+        assert_eq!(
+            metadata.external_url.as_ref().map(|url| url.as_str()),
+            Some("https://wanderers.ai/")
+        );
+        assert_eq!(
+            metadata.background_color,
+            Some(RGB8::new(255, 255, 255).into())
+        );
+        assert!(metadata.animation_url.is_some());
+        assert!(metadata.youtube_url.is_some());
+        assert_eq!(metadata.seller_fee_basis_points, Some(250));
+        assert!(metadata.fee_recipient.is_some());
+
+        let mut level = AttributeEntry::number("Level", 5);
+        level.max_value = Some(10);
+        level.display_type = Some(DisplayType::Number);
+        assert_eq!(metadata.attributes, [level]);
+        assert!(metadata.extra.is_empty());
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(value["external_url"], "https://wanderers.ai/");
+        assert_eq!(value["attributes"][0]["trait_type"], "Level");
+        assert!(value.get("externalUrl").is_none());
+        assert!(value["attributes"][0].get("traitType").is_none());
+        assert_eq!(serde_json::from_value::<Metadata>(value).unwrap(), metadata);
+    }
+
+    #[test]
+    fn snake_case_precedence() {
+        let s = fixtures::metadata_json_with(json!({
+            "externalUrl": "https://example.com/camel",
+            "external_url": "https://example.com/snake",
+            "attributes": [{ "traitType": "Camel", "trait_type": "Snake", "value": 1 }]
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(
+            metadata.external_url.as_ref().map(|url| url.as_str()),
+            Some("https://example.com/snake")
+        );
+        assert_eq!(metadata.attributes[0].trait_type(), Some("Snake"));
+    }
+
+    #[test]
+    fn traits() {
+        let s = r#"
+        {
+            "name": "CryptoKitty #1",
+            "description": "Genesis",
+            "image": "https://img.cryptokitties.co/0x06012c8cf97bead5deae237070f9587f8e7a266d/1.png",
+            "traits": [
+                { "trait_type": "fur", "value": "ragamuffin" },
+                { "trait_type": "generation", "value": 0 }
+            ]
+        }
+        "#;
+        let metadata = serde_json::from_str::<Metadata>(s).unwrap();
+        assert_eq!(
+            metadata.attributes,
+            [
+                AttributeEntry::string("fur", "ragamuffin"),
+                AttributeEntry::new("generation", 0),
+            ]
+        );
+        assert!(metadata.extra.is_empty());
+
+        let value = serde_json::to_value(&metadata).unwrap();
+        assert!(value.get("traits").is_none());
+        assert_eq!(value["attributes"][0]["value"], "ragamuffin");
+        assert_eq!(serde_json::from_value::<Metadata>(value).unwrap(), metadata);
+    }
+
+    #[test]
+    fn attributes_and_traits() {
+        let s = fixtures::metadata_json_with(json!({
+            "traits": [{ "trait_type": "Core", "value": "Nebula" }],
+            "attributes": [{ "trait_type": "Core", "value": "Vortex" }]
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(
+            metadata.attributes,
+            [
+                AttributeEntry::string("Core", "Vortex"),
+                AttributeEntry::string("Core", "Nebula"),
+            ]
+        );
+        assert!(serde_json::from_str::<crate::StrictMetadata>(&s).is_ok());
+
+        let s = fixtures::metadata_json_with(json!({
+            "traits": [{ "trait_type": "Core", "value": "Vortex" }],
+            "attributes": [{ "trait_type": "Core", "value": "Vortex" }]
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(
+            metadata.attributes,
+            [AttributeEntry::string("Core", "Vortex")]
+        );
+
+        let s = fixtures::metadata_json_with(json!({
+            "traits": [{ "trait_type": "Core", "value": "Nebula" }],
+            "attributes": []
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(
+            metadata.attributes,
+            [AttributeEntry::string("Core", "Nebula")]
+        );
+    }
+
+    #[test]
+    fn attribute_map() {
+        let map = fixtures::metadata_json_with(json!({
+            "attributes": { "Core": "Vortex", "Level": 3 }
+        }));
+        let array = fixtures::metadata_json_with(json!({
+            "attributes": [
+                { "trait_type": "Core", "value": "Vortex" },
+                { "trait_type": "Level", "value": 3 }
+            ]
+        }));
+        let metadata = serde_json::from_value::<Metadata>(map).unwrap();
+        assert_eq!(
+            metadata,
+            serde_json::from_value::<Metadata>(array.clone()).unwrap()
+        );
+        assert_eq!(serde_json::to_value(&metadata).unwrap(), array);
+    }
+
+    #[test]
+    fn null_attributes() {
+        let metadata = |attributes: Option<serde_json::Value>| {
+            let mut value = fixtures::metadata_json();
+            if let Some(attributes) = attributes {
+                value["attributes"] = attributes;
+            }
+            serde_json::from_value::<Metadata>(value).unwrap()
+        };
+
+        for attributes in [Some(serde_json::Value::Null), None, Some(json!([]))] {
+            let metadata = metadata(attributes);
+            assert!(metadata.attributes.is_empty());
+            assert!(serde_json::to_value(&metadata)
+                .unwrap()
+                .get("attributes")
+                .is_none());
+        }
+
+        let metadata = metadata(Some(json!([{ "trait_type": "Core", "value": "Vortex" }])));
+        assert_eq!(
+            metadata.attributes,
+            [AttributeEntry::string("Core", "Vortex")]
+        );
+    }
+
+    #[test]
+    fn empty_urls() {
+        let s = fixtures::metadata_json_with(json!({
+            "external_url": "",
+            "animation_url": "",
+            "youtube_url": ""
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(metadata.external_url, None);
+        assert_eq!(metadata.animation_url, None);
+        assert_eq!(metadata.youtube_url, None);
+
+        let s = fixtures::metadata_json_with(json!({
+            "external_url": "https://wanderers.ai/",
+            "animation_url": fixtures::IMAGE
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert_eq!(
+            metadata.external_url.as_ref().map(|url| url.as_str()),
+            Some("https://wanderers.ai/")
+        );
+        assert_eq!(metadata.animation_url, Some(RawUrl::from(fixtures::IMAGE)));
+
+        let s = fixtures::metadata_json_with(json!({
+            "youtube_url": "not a url"
+        }))
+        .to_string();
+        assert!(serde_json::from_str::<Metadata>(&s).is_err());
+    }
+}
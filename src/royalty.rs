@@ -0,0 +1,162 @@
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{contract::MAX_BASIS_POINTS, Metadata};
+
+/// A royalty paid on secondary sales, as found in Rarible's `royalties` array.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Royalty {
+    /// `0x`-prefixed address of the account the royalty is paid to.
+    pub account: String,
+    /// Royalty in basis points, where 100 is a 1% fee.
+    pub value: u32,
+}
+
+impl Royalty {
+    /// Creates a royalty paid to `account`.
+    pub fn new(account: impl Into<String>, value: u32) -> Self {
+        Royalty {
+            account: account.into(),
+            value,
+        }
+    }
+}
+
+impl Metadata {
+    /// Returns the royalty owed on `sale_price` according to
+    /// [`seller_fee_basis_points`](Metadata::seller_fee_basis_points), rounded down as EIP-2981's `royaltyInfo`
+    /// would.
+    ///
+    /// Returns `None` if no fee is set or the fee exceeds 10000 basis points. The calculation cannot overflow.
+    pub fn royalty_for(&self, sale_price: u128) -> Option<u128> {
+        let basis_points = self.seller_fee_basis_points?;
+        if basis_points > MAX_BASIS_POINTS {
+            return None;
+        }
+        let (basis_points, max) = (u128::from(basis_points), u128::from(MAX_BASIS_POINTS));
+        Some(sale_price / max * basis_points + sale_price % max * basis_points / max)
+    }
+
+    /// Returns the sum of all [`royalties`](Metadata::royalties), in basis points.
+    pub fn royalty_basis_points(&self) -> u32 {
+        self.royalties
+            .iter()
+            .fold(0, |total, royalty| total.saturating_add(royalty.value))
+    }
+
+    /// Returns the sum of all [`royalties`](Metadata::royalties), as a percentage.
+    pub fn royalty_percentage(&self) -> f64 {
+        f64::from(self.royalty_basis_points()) / 100.0
+    }
+}
+
+/// Checks that `account` is `0x` followed by 40 hexadecimal digits.
+pub(crate) fn is_address(account: &str) -> bool {
+    account
+        .strip_prefix("0x")
+        .map(|digits| digits.len() == 40 && digits.chars().all(|c| c.is_ascii_hexdigit()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Metadata, Royalty, ValidationError};
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn rarible_example() {
+        let s = r#"
+        {
+            "name": "Rarible Test Token",
+            "description": "Minted on Rarible",
+            "image": "ipfs://ipfs/QmSqC7TfyQc8wgxa8uBwzoJCmWyLbzxBx8d35pKhqWzYyV/image.png",
+            "external_url": "https://rarible.com/token/0x60f80121c31a0d46b5279700f9df786054aa5ee5:123913",
+            "attributes": [{ "trait_type": "Color", "value": "Blue" }],
+            "royalties": [
+                { "account": "0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6", "value": 1000 },
+                { "account": "0x60F80121C31A0d46B5279700f9DF786054aa5eE5", "value": 250 }
+            ],
+            "tags": ["art"]
+        }
+        "#;
+        let metadata = serde_json::from_str::<Metadata>(s).unwrap();
+        assert_eq!(metadata.royalties[1].value, 250);
+        assert_eq!(metadata.royalty_basis_points(), 1250);
+        assert_eq!(metadata.royalty_percentage(), 12.5);
+        assert_eq!(metadata.validate(), Ok(()));
+
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::from_str::<serde_json::Value>(s).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_royalties() {
+        let metadata = Metadata {
+            name: "Rarible Test Token".into(),
+            description: "Minted on Rarible".into(),
+            royalties: vec![
+                Royalty::new("0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6", 9000),
+                Royalty::new("a95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6", 1001),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            metadata.validate(),
+            Err(vec![
+                ValidationError::FeeTooHigh(10_001),
+                ValidationError::InvalidAddress("a95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn royalty_for() {
+        let metadata = Metadata {
+            seller_fee_basis_points: Some(250),
+            ..Default::default()
+        };
+        assert_eq!(metadata.royalty_for(10_000), Some(250));
+        assert_eq!(metadata.royalty_for(1_999), Some(49));
+        assert_eq!(metadata.royalty_for(39), Some(0));
+        assert_eq!(
+            metadata.royalty_for(1_000_000_000_000_000_000_000_000_000),
+            Some(25_000_000_000_000_000_000_000_000)
+        );
+        assert_eq!(
+            Metadata {
+                seller_fee_basis_points: Some(5_000),
+                ..Default::default()
+            }
+            .royalty_for(u128::MAX),
+            Some(u128::MAX / 2)
+        );
+        assert_eq!(
+            Metadata {
+                seller_fee_basis_points: Some(10_000),
+                ..Default::default()
+            }
+            .royalty_for(u128::MAX),
+            Some(u128::MAX)
+        );
+
+        assert_eq!(Metadata::default().royalty_for(10_000), None);
+        let metadata = Metadata {
+            seller_fee_basis_points: Some(10_001),
+            ..crate::fixtures::metadata()
+        };
+        assert_eq!(metadata.royalty_for(10_000), None);
+        assert_eq!(
+            metadata.validate(),
+            Err(vec![ValidationError::FeeTooHigh(10_001)])
+        );
+    }
+}
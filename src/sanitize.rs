@@ -0,0 +1,267 @@
+use std::borrow::Cow;
+
+use crate::Metadata;
+
+/// Elements whose contents are dropped along with their tags.
+const DROPPED_ELEMENTS: &[&str] = &["script", "style"];
+
+/// An HTML tag or comment at the start of some text.
+struct Tag<'a> {
+    /// Length of the tag in bytes, including the angle brackets.
+    len: usize,
+    /// Name of the element, which is empty for comments.
+    name: &'a str,
+    /// Whether this is a closing tag, such as `</p>`.
+    closing: bool,
+    /// Whether the tag ends with a `>`, rather than running to the end of the text.
+    terminated: bool,
+}
+
+impl<'a> Tag<'a> {
+    /// Parses the tag at the start of `text`.
+    ///
+    /// A `<` that is not followed by a tag name, such as in `a < b`, and markdown autolinks such as
+    /// `<https://example.com>` are not tags. A tag without a closing `>` runs to the end of the text.
+    fn parse(text: &'a str) -> Option<Self> {
+        if let Some(comment) = text.strip_prefix("<!--") {
+            let len = comment.find("-->").map_or(text.len(), |end| end + 7);
+            return Some(Tag {
+                len,
+                name: "",
+                closing: false,
+                terminated: true,
+            });
+        }
+
+        let inner = text.strip_prefix('<')?;
+        let name = inner.strip_prefix(['/', '!']).unwrap_or(inner);
+        let name_len = name
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+            .unwrap_or(name.len());
+        let (name, after) = name.split_at(name_len);
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic())
+            || !after.starts_with(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/')
+        {
+            return None;
+        }
+
+        let end = after.find('>');
+        Some(Tag {
+            len: end.map_or(text.len(), |end| text.len() - after.len() + end + 1),
+            name,
+            closing: inner.starts_with('/'),
+            terminated: end.is_some(),
+        })
+    }
+
+    /// Returns the text after the closing tag matching this one, or an empty string if it is never closed.
+    fn skip_contents<'b>(&self, text: &'b str) -> &'b str {
+        for (start, _) in text.match_indices('<') {
+            if let Some(tag) = Tag::parse(&text[start..]) {
+                if tag.closing && tag.name.eq_ignore_ascii_case(self.name) {
+                    return &text[start + tag.len..];
+                }
+            }
+        }
+        ""
+    }
+}
+
+/// Checks that `c` is a control character other than a newline or tab, or a Unicode bidirectional override
+/// that could be used to disguise the text around it.
+fn is_stripped(c: char) -> bool {
+    (c.is_control() && c != '\n' && c != '\t')
+        || matches!(c, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Collapses runs of whitespace within a line into a single space, and runs of blank lines into one.
+///
+/// Indentation and the two trailing spaces of a markdown line break are kept, while leading and trailing blank
+/// lines are removed.
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut blank = false;
+    for line in text.trim_matches(|c: char| c.is_whitespace()).split('\n') {
+        let content = line.trim();
+        if content.is_empty() {
+            blank = true;
+            continue;
+        }
+        if !collapsed.is_empty() {
+            collapsed.push_str(if blank { "\n\n" } else { "\n" });
+        }
+        blank = false;
+
+        collapsed.push_str(&line[..line.len() - line.trim_start().len()]);
+        for (i, word) in content.split_whitespace().enumerate() {
+            if i > 0 {
+                collapsed.push(' ');
+            }
+            collapsed.push_str(word);
+        }
+        if line.ends_with("  ") {
+            collapsed.push_str("  ");
+        }
+    }
+    collapsed
+}
+
+/// Removes tags and control characters from `text` in a single pass.
+///
+/// Removing a tag can join the text around it into a new tag, so this is repeated by [`sanitize`] until nothing
+/// changes.
+fn strip(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some(tag) = Tag::parse(rest) {
+            if !tag.terminated {
+                stripped.push_str("&lt;");
+                rest = &rest[1..];
+                continue;
+            }
+            rest = &rest[tag.len..];
+            if tag.name.eq_ignore_ascii_case("br") {
+                stripped.push('\n');
+            } else if !tag.closing
+                && DROPPED_ELEMENTS
+                    .iter()
+                    .any(|element| tag.name.eq_ignore_ascii_case(element))
+            {
+                rest = tag.skip_contents(rest);
+            }
+            continue;
+        }
+
+        if !is_stripped(c) {
+            stripped.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    stripped
+}
+
+/// Strips HTML tags and control characters from `text`, and collapses excessive whitespace.
+///
+/// The contents of `<script>` and `<style>` elements are removed along with their tags, `<br>` becomes a
+/// newline and HTML comments are dropped. Tags are stripped repeatedly, so that nested tags such as
+/// `<<b>script>` cannot reassemble into a new one, and the `<` of a tag without a closing `>` is escaped as
+/// `&lt;`. Markdown, including autolinks such as `<https://example.com>`, is left alone. Sanitizing the result
+/// again changes nothing. Returns [`Cow::Borrowed`] if nothing changed.
+pub fn sanitize(text: &str) -> Cow<'_, str> {
+    let mut stripped = strip(text);
+    loop {
+        let again = strip(&stripped);
+        if again == stripped {
+            break;
+        }
+        stripped = again;
+    }
+
+    let sanitized = collapse_whitespace(&stripped);
+    if sanitized == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(sanitized)
+    }
+}
+
+impl Metadata {
+    /// Sanitizes the [`description`](Metadata::description) in place with [`sanitize`], returning whether it
+    /// changed.
+    pub fn sanitize_description(&mut self) -> bool {
+        match sanitize(&self.description) {
+            Cow::Borrowed(_) => false,
+            Cow::Owned(description) => {
+                self.description = description;
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::{sanitize, Metadata};
+
+    #[test]
+    fn script_tag() {
+        assert_eq!(
+            sanitize("Friendly <script type=\"text/javascript\">alert('<b>hi</b>')</script>OpenSea Creature"),
+            "Friendly OpenSea Creature"
+        );
+        assert_eq!(sanitize("Unclosed <SCRIPT>alert(1)"), "Unclosed");
+        assert_eq!(
+            sanitize("<p>Line one<br/>Line <b>two</b></p><!-- hidden -->"),
+            "Line one\nLine two"
+        );
+    }
+
+    #[test]
+    fn nested_tags() {
+        assert_eq!(sanitize("<<b>script>alert(1)<</b>/script>"), "");
+        assert_eq!(
+            sanitize("Rocketeer <<i>img src=x onerror=alert(1)>X"),
+            "Rocketeer X"
+        );
+        assert_eq!(sanitize("<<<b>b>i>Rocketeer X</i>"), "Rocketeer X");
+    }
+
+    #[test]
+    fn unterminated_tag() {
+        assert_eq!(
+            sanitize("Rocketeer X<img src=x onerror=alert(1)"),
+            "Rocketeer X&lt;img src=x onerror=alert(1)"
+        );
+        assert_eq!(sanitize("<<b>img src=x"), "&lt;img src=x");
+    }
+
+    #[test]
+    fn idempotent() {
+        for text in [
+            "<<b>script>alert(1)<</b>/script>",
+            "<<i>img src=x onerror=alert(1)>",
+            "Rocketeer X<img src=x onerror=alert(1)",
+            "<p>Line one<br/>Line <b>two</b></p><!-- hidden -->",
+            "  Visit   this\tplanet \n\n\n\nand get a free   Rocketeer  \nNFT\n\n",
+            "**Bold** and <https://opensea.io>, 1 < 2 and 3 > 2",
+        ] {
+            let once = sanitize(text);
+            assert_eq!(sanitize(&once), once, "{}", text);
+        }
+    }
+
+    #[test]
+    fn control_characters() {
+        assert_eq!(sanitize("Rocketeer\u{0000} X"), "Rocketeer X");
+        assert_eq!(sanitize("Rocketeer\r\nX\u{202e}"), "Rocketeer\nX");
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(
+            sanitize("  Visit   this\tplanet \n\n\n\nand get a free   Rocketeer  \nNFT\n\n"),
+            "Visit this planet\n\nand get a free Rocketeer  \nNFT"
+        );
+    }
+
+    #[test]
+    fn markdown_untouched() {
+        let description = "**Bold** and _italic_ with a [link](https://wanderers.ai/) and <https://opensea.io>.\n\n\
+            - 1 < 2\n- 3 > 2\n\n    let code = 1;";
+        assert!(matches!(sanitize(description), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn sanitize_description() {
+        let mut metadata = Metadata {
+            description: "Friendly OpenSea Creature<script>alert(1)</script>".into(),
+            ..Default::default()
+        };
+        assert!(metadata.sanitize_description());
+        assert_eq!(metadata.description, "Friendly OpenSea Creature");
+        assert!(!metadata.sanitize_description());
+    }
+}
@@ -0,0 +1,38 @@
+use schemars::{schema_for, Schema};
+
+use crate::Metadata;
+
+impl Metadata {
+    /// Returns a [JSON Schema](https://json-schema.org/) describing the documents that [`Metadata`]
+    /// deserializes from, including the alternative spellings it accepts.
+    pub fn json_schema() -> Schema {
+        schema_for!(Metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::Metadata;
+
+    #[test]
+    fn json_schema() {
+        let schema = Metadata::json_schema().to_value();
+        assert_eq!(schema["required"], json!(["name", "description"]));
+        assert!(schema["anyOf"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({ "required": ["image"] })));
+
+        let properties = &schema["properties"];
+        for property in ["name", "description", "image", "image_data", "attributes"] {
+            assert!(properties.get(property).is_some(), "missing {}", property);
+        }
+        assert!(properties.get("externalUrl").is_some());
+        assert_eq!(
+            properties["attributes"]["anyOf"].as_array().unwrap().len(),
+            3
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::BTreeMap;
+
+use serde::{
+    ser::{SerializeMap, Serializer},
+    Serialize,
+};
+use url::Url;
+
+use crate::{
+    strict::FIELDS, AttributeEntry, CollectionInfo, Metadata, PartialMetadata, Properties, RawUrl,
+    Royalty,
+};
+
+/// Order in which the keys of [`Metadata`] are serialized.
+///
+/// With either profile, unset optional fields and empty arrays are omitted, and the [`extra`](Metadata::extra)
+/// fields follow the known fields, sorted by key. Extra fields named like a known field or one of its aliases,
+/// such as `name` or `imageData`, are skipped, since the output could not be deserialized again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SerializeProfile {
+    /// `name`, `description`, `image`, `image_data`, `external_url`, `attributes`, `background_color`,
+    /// `animation_url`, `youtube_url`, `collection`, `royalties`, `seller_fee_basis_points`, `fee_recipient` and
+    /// `properties`.
+    ///
+    /// This is the order used by the [`Serialize`] implementation of [`Metadata`].
+    #[default]
+    Default,
+    /// The order of the [OpenSea metadata standard](https://docs.opensea.io/docs/metadata-standards), with the
+    /// `attributes` directly after the fields shown for an item: `name`, `description`, `image`, `attributes`,
+    /// `external_url`, `image_data`, `background_color`, `animation_url` and `youtube_url`, followed by
+    /// `collection`, `royalties`, `seller_fee_basis_points`, `fee_recipient` and `properties`.
+    OpenSea,
+}
+
+/// A known field of [`Metadata`].
+#[derive(Clone, Copy)]
+enum Key {
+    Name,
+    Description,
+    Image,
+    ImageData,
+    ExternalUrl,
+    Attributes,
+    BackgroundColor,
+    AnimationUrl,
+    YoutubeUrl,
+    Collection,
+    Royalties,
+    SellerFeeBasisPoints,
+    FeeRecipient,
+    Properties,
+}
+
+impl SerializeProfile {
+    fn keys(self) -> &'static [Key] {
+        match self {
+            SerializeProfile::Default => &[
+                Key::Name,
+                Key::Description,
+                Key::Image,
+                Key::ImageData,
+                Key::ExternalUrl,
+                Key::Attributes,
+                Key::BackgroundColor,
+                Key::AnimationUrl,
+                Key::YoutubeUrl,
+                Key::Collection,
+                Key::Royalties,
+                Key::SellerFeeBasisPoints,
+                Key::FeeRecipient,
+                Key::Properties,
+            ],
+            SerializeProfile::OpenSea => &[
+                Key::Name,
+                Key::Description,
+                Key::Image,
+                Key::Attributes,
+                Key::ExternalUrl,
+                Key::ImageData,
+                Key::BackgroundColor,
+                Key::AnimationUrl,
+                Key::YoutubeUrl,
+                Key::Collection,
+                Key::Royalties,
+                Key::SellerFeeBasisPoints,
+                Key::FeeRecipient,
+                Key::Properties,
+            ],
+        }
+    }
+}
+
+/// The fields of [`Metadata`] or [`PartialMetadata`], borrowed for serialization.
+struct Fields<'a> {
+    name: Option<&'a str>,
+    description: Option<&'a str>,
+    image: Option<&'a RawUrl>,
+    image_data: Option<&'a str>,
+    external_url: Option<&'a Url>,
+    attributes: &'a [AttributeEntry],
+    background_color: Option<String>,
+    animation_url: Option<&'a RawUrl>,
+    youtube_url: Option<&'a Url>,
+    collection: Option<&'a CollectionInfo>,
+    royalties: &'a [Royalty],
+    seller_fee_basis_points: Option<u32>,
+    fee_recipient: Option<&'a str>,
+    properties: Option<&'a Properties>,
+    extra: &'a BTreeMap<String, serde_json::Value>,
+}
+
+impl<'a> From<&'a Metadata> for Fields<'a> {
+    fn from(metadata: &'a Metadata) -> Self {
+        Fields {
+            name: Some(&metadata.name),
+            description: Some(&metadata.description),
+            image: metadata.image.as_ref(),
+            image_data: metadata.image_data.as_deref(),
+            external_url: metadata.external_url.as_ref(),
+            attributes: &metadata.attributes,
+            background_color: metadata
+                .background_color
+                .map(|color| color.to_hex_with_alpha(metadata.background_color_alpha)),
+            animation_url: metadata.animation_url.as_ref(),
+            youtube_url: metadata.youtube_url.as_ref(),
+            collection: metadata.collection.as_ref(),
+            royalties: &metadata.royalties,
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            fee_recipient: metadata.fee_recipient.as_deref(),
+            properties: metadata.properties.as_ref(),
+            extra: &metadata.extra,
+        }
+    }
+}
+
+impl<'a> From<&'a PartialMetadata> for Fields<'a> {
+    fn from(partial: &'a PartialMetadata) -> Self {
+        Fields {
+            name: partial.name.as_deref(),
+            description: partial.description.as_deref(),
+            image: partial.image.as_ref(),
+            image_data: partial.image_data.as_deref(),
+            external_url: partial.external_url.as_ref(),
+            attributes: &partial.attributes,
+            background_color: partial
+                .background_color
+                .map(|color| color.to_hex_with_alpha(partial.background_color_alpha)),
+            animation_url: partial.animation_url.as_ref(),
+            youtube_url: partial.youtube_url.as_ref(),
+            collection: partial.collection.as_ref(),
+            royalties: &partial.royalties,
+            seller_fee_basis_points: partial.seller_fee_basis_points,
+            fee_recipient: partial.fee_recipient.as_deref(),
+            properties: partial.properties.as_ref(),
+            extra: &partial.extra,
+        }
+    }
+}
+
+impl Fields<'_> {
+    fn serialize<S: Serializer>(
+        &self,
+        profile: SerializeProfile,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        fn entry<M: SerializeMap, T: Serialize>(
+            map: &mut M,
+            key: &str,
+            value: Option<T>,
+        ) -> Result<(), M::Error> {
+            match value {
+                Some(value) => map.serialize_entry(key, &value),
+                None => Ok(()),
+            }
+        }
+        fn non_empty<T>(values: &[T]) -> Option<&[T]> {
+            Some(values).filter(|values| !values.is_empty())
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        for key in profile.keys() {
+            match key {
+                Key::Name => entry(&mut map, "name", self.name)?,
+                Key::Description => entry(&mut map, "description", self.description)?,
+                Key::Image => entry(&mut map, "image", self.image)?,
+                Key::ImageData => entry(&mut map, "image_data", self.image_data)?,
+                Key::ExternalUrl => entry(&mut map, "external_url", self.external_url)?,
+                Key::Attributes => entry(&mut map, "attributes", non_empty(self.attributes))?,
+                Key::BackgroundColor => {
+                    entry(&mut map, "background_color", self.background_color.as_ref())?
+                }
+                Key::AnimationUrl => entry(&mut map, "animation_url", self.animation_url)?,
+                Key::YoutubeUrl => entry(&mut map, "youtube_url", self.youtube_url)?,
+                Key::Collection => entry(&mut map, "collection", self.collection)?,
+                Key::Royalties => entry(&mut map, "royalties", non_empty(self.royalties))?,
+                Key::SellerFeeBasisPoints => entry(
+                    &mut map,
+                    "seller_fee_basis_points",
+                    self.seller_fee_basis_points,
+                )?,
+                Key::FeeRecipient => entry(&mut map, "fee_recipient", self.fee_recipient)?,
+                Key::Properties => entry(&mut map, "properties", self.properties)?,
+            }
+        }
+        for (key, value) in self.extra {
+            if !FIELDS.contains(&key.as_str()) {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// Serializes the keys in the order of [`SerializeProfile::Default`].
+impl Serialize for Metadata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_with(SerializeProfile::Default, serializer)
+    }
+}
+
+/// Serializes the keys in the order of [`SerializeProfile::Default`], omitting a missing name or description.
+impl Serialize for PartialMetadata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Fields::from(self).serialize(SerializeProfile::Default, serializer)
+    }
+}
+
+impl Metadata {
+    /// Serializes the metadata with the key order of `profile`.
+    pub fn serialize_with<S: Serializer>(
+        &self,
+        profile: SerializeProfile,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Fields::from(self).serialize(profile, serializer)
+    }
+
+    /// Serializes the metadata as compact JSON with the key order of `profile`.
+    pub fn to_string_with(&self, profile: SerializeProfile) -> Result<String, serde_json::Error> {
+        let mut bytes = Vec::new();
+        self.serialize_with(profile, &mut serde_json::Serializer::new(&mut bytes))?;
+        Ok(String::from_utf8(bytes).expect("serde_json should produce UTF-8"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rgb::RGB8;
+    use serde_json::json;
+    use url::Url;
+
+    use crate::{
+        fixtures, AttributeEntry, BackgroundColor, CollectionInfo, Metadata, Properties, RawUrl,
+        Royalty, SerializeProfile,
+    };
+
+    fn metadata() -> Metadata {
+        let mut metadata = Metadata {
+            image_data: Some("<svg></svg>".into()),
+            external_url: Some(Url::parse("https://wanderers.ai/").unwrap()),
+            attributes: vec![AttributeEntry::string("Core", "Vortex")],
+            background_color: Some(BackgroundColor(RGB8::new(0xf2, 0xf2, 0xf2))),
+            animation_url: Some(RawUrl::from(fixtures::IMAGE)),
+            youtube_url: Some(Url::parse("https://www.youtube.com/watch?v=dQw4w9WgXcQ").unwrap()),
+            collection: Some(CollectionInfo::new("Planetpass")),
+            royalties: vec![Royalty::new(
+                "0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6",
+                250,
+            )],
+            seller_fee_basis_points: Some(250),
+            fee_recipient: Some("0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6".into()),
+            properties: Some(Properties::from(
+                json!({ "generation": 2 }).as_object().unwrap().clone(),
+            )),
+            ..fixtures::metadata()
+        };
+        metadata.extra.insert("tags".into(), json!(["art"]));
+        metadata.extra.insert("edition".into(), json!(1));
+        metadata
+    }
+
+    #[test]
+    fn opensea_order() {
+        assert_eq!(
+            metadata()
+                .to_string_with(SerializeProfile::OpenSea)
+                .unwrap(),
+            format!(
+                concat!(
+                    r#"{{"name":"{name}","description":"{description}","image":"{image}","#,
+                    r#""attributes":[{{"trait_type":"Core","value":"Vortex"}}],"#,
+                    r#""external_url":"https://wanderers.ai/","#,
+                    r#""image_data":"<svg></svg>","#,
+                    r#""background_color":"f2f2f2","#,
+                    r#""animation_url":"{image}","#,
+                    r#""youtube_url":"https://www.youtube.com/watch?v=dQw4w9WgXcQ","#,
+                    r#""collection":{{"name":"Planetpass"}},"#,
+                    r#""royalties":[{{"account":"0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6","value":250}}],"#,
+                    r#""seller_fee_basis_points":250,"#,
+                    r#""fee_recipient":"0xa95e4e0ab1ad0ba094ec2d6fba4d1d7b6ac2b6f6","#,
+                    r#""properties":{{"generation":2}},"#,
+                    r#""edition":1,"tags":["art"]}}"#,
+                ),
+                name = fixtures::NAME,
+                description = fixtures::DESCRIPTION,
+                image = fixtures::IMAGE,
+            )
+        );
+    }
+
+    #[test]
+    fn default_profile() {
+        let metadata = metadata();
+        let s = metadata.to_string_with(SerializeProfile::Default).unwrap();
+        assert_eq!(s, serde_json::to_string(&metadata).unwrap());
+        assert!(s.starts_with(&format!(
+            concat!(
+                r#"{{"name":"{name}","description":"{description}","image":"{image}","#,
+                r#""image_data":"<svg></svg>","#,
+                r#""external_url":"https://wanderers.ai/","#,
+                r#""attributes":"#,
+            ),
+            name = fixtures::NAME,
+            description = fixtures::DESCRIPTION,
+            image = fixtures::IMAGE,
+        )));
+        assert_eq!(serde_json::from_str::<Metadata>(&s).unwrap(), metadata);
+    }
+
+    #[test]
+    fn omits_unset_fields() {
+        let metadata = Metadata::builder()
+            .image(fixtures::IMAGE)
+            .name(fixtures::NAME)
+            .description(fixtures::DESCRIPTION)
+            .build()
+            .unwrap();
+        assert_eq!(
+            metadata.to_string_with(SerializeProfile::OpenSea).unwrap(),
+            format!(
+                r#"{{"name":"{}","description":"{}","image":"{}"}}"#,
+                fixtures::NAME,
+                fixtures::DESCRIPTION,
+                fixtures::IMAGE,
+            )
+        );
+    }
+
+    #[test]
+    fn skips_known_extra_fields() {
+        let mut metadata = fixtures::metadata();
+        metadata.extra.insert("name".into(), json!("evil"));
+        metadata.extra.insert("imageData".into(), json!("<svg/>"));
+        metadata.extra.insert("edition".into(), json!(1));
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"name":"{}","description":"{}","image":"{}","edition":1}}"#,
+                fixtures::NAME,
+                fixtures::DESCRIPTION,
+                fixtures::IMAGE,
+            )
+        );
+        let metadata = serde_json::from_str::<Metadata>(&json).unwrap();
+        assert_eq!(metadata.extra.len(), 1);
+    }
+}
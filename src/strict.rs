@@ -0,0 +1,131 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+use crate::Metadata;
+
+/// Fields recognized by [`Metadata`].
+pub(crate) const FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "image",
+    "image_url",
+    "image_data",
+    "external_url",
+    "external_link",
+    "attributes",
+    "traits",
+    "background_color",
+    "animation_url",
+    "youtube_url",
+    "collection",
+    "royalties",
+    "seller_fee_basis_points",
+    "fee_recipient",
+    "properties",
+    "imageData",
+    "externalUrl",
+    "backgroundColor",
+    "animationUrl",
+    "youtubeUrl",
+    "sellerFeeBasisPoints",
+    "feeRecipient",
+];
+
+/// Fields recognized by [`AttributeEntry`](crate::AttributeEntry).
+const ATTRIBUTE_FIELDS: &[&str] = &[
+    "trait_type",
+    "value",
+    "max_value",
+    "display_type",
+    "traitType",
+    "maxValue",
+    "displayType",
+];
+
+/// [`Metadata`] that fails to deserialize if the document contains any field it does not recognize.
+///
+/// Unrecognized fields inside attributes are rejected as well. Plain [`Metadata`] collects unrecognized fields
+/// into [`Metadata::extra`] and [`AttributeEntry::extra`](crate::AttributeEntry::extra) instead. This is useful
+/// for catching typos such as `"imagee"` when ingesting metadata.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct StrictMetadata(pub Metadata);
+
+impl<'de> Deserialize<'de> for StrictMetadata {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let metadata = Metadata::deserialize(deserializer)?;
+        if let Some(field) = metadata.extra.keys().next() {
+            return Err(D::Error::unknown_field(field, FIELDS));
+        }
+        if let Some(field) = metadata
+            .attributes
+            .iter()
+            .find_map(|attribute| attribute.extra.keys().next())
+        {
+            return Err(D::Error::unknown_field(field, ATTRIBUTE_FIELDS));
+        }
+        Ok(StrictMetadata(metadata))
+    }
+}
+
+impl From<Metadata> for StrictMetadata {
+    fn from(metadata: Metadata) -> Self {
+        StrictMetadata(metadata)
+    }
+}
+
+impl From<StrictMetadata> for Metadata {
+    fn from(metadata: StrictMetadata) -> Self {
+        metadata.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{fixtures, Metadata, StrictMetadata};
+
+    #[test]
+    fn unknown_field() {
+        let s = fixtures::metadata_json_with(json!({ "imagee": fixtures::IMAGE })).to_string();
+        let error = serde_json::from_str::<StrictMetadata>(&s).unwrap_err();
+        assert!(error.to_string().contains("unknown field `imagee`"));
+
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert!(metadata.extra.contains_key("imagee"));
+    }
+
+    #[test]
+    fn unknown_attribute_field() {
+        let s = fixtures::metadata_json_with(json!({
+            "attributes": [{ "trait_type": "Core", "value": "Vortex", "trait_typ": "Core" }]
+        }))
+        .to_string();
+        let error = serde_json::from_str::<StrictMetadata>(&s).unwrap_err();
+        assert!(error.to_string().contains("unknown field `trait_typ`"));
+
+        let metadata = serde_json::from_str::<Metadata>(&s).unwrap();
+        assert!(metadata.attributes[0].extra.contains_key("trait_typ"));
+    }
+
+    #[test]
+    fn known_fields() {
+        let s = fixtures::metadata_json_with(json!({
+            "attributes": [{ "trait_type": "Core", "value": "Vortex" }],
+            "background_color": "f2f2f2"
+        }))
+        .to_string();
+        let metadata = serde_json::from_str::<StrictMetadata>(&s).unwrap();
+        assert_eq!(
+            Metadata::from(metadata),
+            serde_json::from_str::<Metadata>(&s).unwrap()
+        );
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use url::Url;
+
+use crate::{Metadata, RawUrl};
+
+/// A 256-bit token ID, stored as big-endian bytes.
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct TokenId(pub [u8; 32]);
+
+impl TokenId {
+    /// Formats the token ID as 64 lowercase hexadecimal digits without a `0x` prefix, as required when
+    /// substituting the ERC-1155 `{id}` placeholder.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+}
+
+impl From<[u8; 32]> for TokenId {
+    fn from(bytes: [u8; 32]) -> Self {
+        TokenId(bytes)
+    }
+}
+
+macro_rules! impl_from_uint {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for TokenId {
+                fn from(id: $t) -> Self {
+                    let mut bytes = [0; 32];
+                    let be = id.to_be_bytes();
+                    bytes[32 - be.len()..].copy_from_slice(&be);
+                    TokenId(bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_uint!(u8, u16, u32, u64, u128);
+
+/// Replaces every ERC-1155 `{id}` placeholder in `uri` with the hexadecimal form of `token_id`.
+///
+/// The percent-encoded form `%7Bid%7D` produced by [`Url`] is also replaced. URIs without a placeholder
+/// are returned unchanged, after being parsed.
+pub fn expand_id_placeholder(
+    uri: &str,
+    token_id: impl Into<TokenId>,
+) -> Result<Url, url::ParseError> {
+    Url::parse(&substitute(uri, token_id.into()))
+}
+
+fn substitute(uri: &str, token_id: TokenId) -> String {
+    let id = token_id.to_hex();
+    ["{id}", "%7Bid%7D", "%7bid%7d"]
+        .iter()
+        .fold(uri.to_owned(), |uri, placeholder| {
+            uri.replace(placeholder, &id)
+        })
+}
+
+impl Metadata {
+    /// Replaces the ERC-1155 `{id}` placeholder in [`image`](Metadata::image),
+    /// [`external_url`](Metadata::external_url) and [`animation_url`](Metadata::animation_url).
+    ///
+    /// See [`expand_id_placeholder`]. The image and animation URLs are substituted as written, so relative URLs
+    /// are supported. Nothing is modified if the external URL fails to parse.
+    pub fn expand_id_placeholders(
+        &mut self,
+        token_id: impl Into<TokenId>,
+    ) -> Result<(), url::ParseError> {
+        let token_id = token_id.into();
+        let expand = |url: &Url| expand_id_placeholder(url.as_str(), token_id);
+
+        let external_url = self.external_url.as_ref().map(expand).transpose()?;
+
+        let substitute = |url: &RawUrl| RawUrl(substitute(url, token_id));
+        self.image = self.image.as_ref().map(substitute);
+        self.external_url = external_url;
+        self.animation_url = self.animation_url.as_ref().map(substitute);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::{expand_id_placeholder, Metadata, TokenId};
+
+    #[test]
+    fn zero() {
+        assert_eq!(
+            expand_id_placeholder("https://example.com/{id}.json", 0u8).unwrap(),
+            Url::parse(
+                "https://example.com/0000000000000000000000000000000000000000000000000000000000000000.json"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn large() {
+        let mut bytes = [0xff; 32];
+        bytes[0] = 0xab;
+        assert_eq!(
+            expand_id_placeholder("https://example.com/{id}.json", TokenId(bytes))
+                .unwrap()
+                .as_str(),
+            "https://example.com/abffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff.json"
+        );
+        assert_eq!(
+            TokenId::from(0x04d2u128).to_hex(),
+            "00000000000000000000000000000000000000000000000000000000000004d2"
+        );
+    }
+
+    #[test]
+    fn query_string() {
+        assert_eq!(
+            expand_id_placeholder("https://example.com/token?id={id}&v=1", 314_592u32)
+                .unwrap()
+                .as_str(),
+            "https://example.com/token?id=000000000000000000000000000000000000000000000000000000000004cce0&v=1"
+        );
+    }
+
+    #[test]
+    fn metadata() {
+        let mut metadata = Metadata::builder()
+            .image("https://s3.amazonaws.com/your-bucket/images/{id}.png")
+            .external_url("https://example.com/")
+            .name("Asset Name")
+            .description("Lorem ipsum...")
+            .build()
+            .unwrap();
+        metadata.expand_id_placeholders(1u8).unwrap();
+
+        assert_eq!(
+            metadata.image.unwrap().as_str(),
+            "https://s3.amazonaws.com/your-bucket/images/0000000000000000000000000000000000000000000000000000000000000001.png"
+        );
+        assert_eq!(
+            metadata.external_url,
+            Some(Url::parse("https://example.com/").unwrap())
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use std::fmt;
+
+use url::Url;
+
+use crate::{extract_cid, Metadata};
+
+/// Rules that every URL in the metadata must follow, checked with [`Metadata::check_urls`].
+///
+/// A new policy allows no scheme at all, places no restriction on hosts, and rejects `data:` URIs and HTTP
+/// IPFS gateway URLs such as `https://ipfs.io/ipfs/<cid>` or `https://<cid>.ipfs.dweb.link/`. Each rule is then
+/// relaxed with the builder methods, such as [`allow_scheme`](UrlPolicy::allow_scheme).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UrlPolicy {
+    schemes: Vec<String>,
+    host_suffixes: Vec<String>,
+    data_uris: bool,
+    ipfs_gateways: bool,
+}
+
+impl UrlPolicy {
+    /// Creates a policy that rejects every URL.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows URLs with the given scheme, such as `"https"` or `"ipfs"`, compared case-insensitively.
+    pub fn allow_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.schemes.push(scheme.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Restricts URLs with a host, such as `https://` URLs, to hosts equal to or under `suffix`.
+    ///
+    /// `"wanderers.ai"` allows `wanderers.ai` and `assets.wanderers.ai`, but not `notwanderers.ai`. Once any
+    /// suffix is added, hosts matching none of them are rejected. `ipfs://` and `ar://` URLs have no host to
+    /// check.
+    pub fn allow_host_suffix(mut self, suffix: impl Into<String>) -> Self {
+        let suffix = suffix.into().to_ascii_lowercase();
+        self.host_suffixes
+            .push(suffix.strip_prefix('.').unwrap_or(&suffix).to_owned());
+        self
+    }
+
+    /// Sets whether `data:` URIs are allowed, regardless of [`allow_scheme`](UrlPolicy::allow_scheme).
+    pub fn allow_data_uris(mut self, allow: bool) -> Self {
+        self.data_uris = allow;
+        self
+    }
+
+    /// Sets whether HTTP IPFS gateway URLs are allowed, which are path or subdomain gateway URLs with a valid
+    /// CID as recognized by [`extract_cid`].
+    ///
+    /// Allowed gateway URLs are still subject to [`allow_host_suffix`](UrlPolicy::allow_host_suffix), so the
+    /// gateway hosts must be allowed as well. When gateway URLs are not allowed, they are rejected even on an
+    /// allowed host, so that `ipfs://` is used instead.
+    pub fn allow_ipfs_gateways(mut self, allow: bool) -> Self {
+        self.ipfs_gateways = allow;
+        self
+    }
+
+    /// Checks a single URL against the policy.
+    pub fn check(&self, url: &str) -> Result<(), UrlRule> {
+        if url
+            .get(..5)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+        {
+            return if self.data_uris {
+                Ok(())
+            } else {
+                Err(UrlRule::DataUri)
+            };
+        }
+
+        let parsed = match Url::parse(url) {
+            Ok(parsed) => parsed,
+            Err(url::ParseError::RelativeUrlWithoutBase) => return Err(UrlRule::Relative),
+            Err(_) => return Err(UrlRule::Invalid),
+        };
+        if !self.schemes.iter().any(|scheme| scheme == parsed.scheme()) {
+            return Err(UrlRule::Scheme(parsed.scheme().to_owned()));
+        }
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Ok(());
+        }
+
+        if !self.ipfs_gateways && extract_cid(url).is_some() {
+            return Err(UrlRule::IpfsGateway);
+        }
+        let host = parsed.host_str().unwrap_or_default();
+        let allowed = self.host_suffixes.is_empty()
+            || self.host_suffixes.iter().any(|suffix| {
+                host == suffix
+                    || host
+                        .strip_suffix(suffix.as_str())
+                        .is_some_and(|subdomain| subdomain.ends_with('.'))
+            });
+        if allowed {
+            Ok(())
+        } else {
+            Err(UrlRule::Host(host.to_owned()))
+        }
+    }
+}
+
+/// The rule of a [`UrlPolicy`] that a URL broke.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlRule {
+    /// The scheme is not allowed.
+    Scheme(String),
+    /// The host is not under any allowed suffix.
+    Host(String),
+    /// `data:` URIs are not allowed.
+    DataUri,
+    /// HTTP IPFS gateway URLs are not allowed.
+    IpfsGateway,
+    /// The URL is relative.
+    Relative,
+    /// The URL could not be parsed.
+    Invalid,
+}
+
+impl fmt::Display for UrlRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlRule::Scheme(scheme) => write!(f, "scheme `{}` is not allowed", scheme),
+            UrlRule::Host(host) => write!(f, "host `{}` is not allowed", host),
+            UrlRule::DataUri => write!(f, "data URIs are not allowed"),
+            UrlRule::IpfsGateway => write!(f, "IPFS gateway URLs are not allowed"),
+            UrlRule::Relative => write!(f, "relative URLs are not allowed"),
+            UrlRule::Invalid => write!(f, "the URL is invalid"),
+        }
+    }
+}
+
+/// A URL in the metadata that breaks a [`UrlPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlViolation {
+    /// Name of the field, such as `"image"`.
+    pub field: &'static str,
+    /// The offending URL.
+    pub url: String,
+    /// The rule that the URL broke.
+    pub rule: UrlRule,
+}
+
+impl fmt::Display for UrlViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} `{}`: {}", self.field, self.url, self.rule)
+    }
+}
+
+impl std::error::Error for UrlViolation {}
+
+impl Metadata {
+    /// Checks [`image`](Metadata::image), [`external_url`](Metadata::external_url),
+    /// [`animation_url`](Metadata::animation_url) and [`youtube_url`](Metadata::youtube_url) against `policy`,
+    /// returning every violation found.
+    pub fn check_urls(&self, policy: &UrlPolicy) -> Result<(), Vec<UrlViolation>> {
+        let urls = [
+            ("image", self.image.as_ref().map(|url| url.as_str())),
+            ("external_url", self.external_url.as_ref().map(Url::as_str)),
+            (
+                "animation_url",
+                self.animation_url.as_ref().map(|url| url.as_str()),
+            ),
+            ("youtube_url", self.youtube_url.as_ref().map(Url::as_str)),
+        ];
+        let violations = urls
+            .into_iter()
+            .filter_map(|(field, url)| {
+                let url = url?;
+                let rule = policy.check(url).err()?;
+                Some(UrlViolation {
+                    field,
+                    url: url.to_owned(),
+                    rule,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::{Metadata, RawUrl, UrlPolicy, UrlRule, UrlViolation};
+
+    const CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    fn policy() -> UrlPolicy {
+        UrlPolicy::new()
+            .allow_scheme("ipfs")
+            .allow_scheme("ar")
+            .allow_scheme("HTTPS")
+            .allow_host_suffix(".wanderers.ai")
+    }
+
+    fn violation(field: &'static str, url: &str, rule: UrlRule) -> UrlViolation {
+        UrlViolation {
+            field,
+            url: url.into(),
+            rule,
+        }
+    }
+
+    #[test]
+    fn allowed() {
+        let metadata = Metadata {
+            image: Some(RawUrl(format!("ipfs://{}/0.png", CID))),
+            external_url: Some(Url::parse("https://wanderers.ai/").unwrap()),
+            animation_url: Some(RawUrl::from(crate::fixtures::IMAGE)),
+            youtube_url: None,
+            ..Default::default()
+        };
+        assert_eq!(metadata.check_urls(&policy()), Ok(()));
+
+        let metadata = Metadata {
+            image: Some(RawUrl::from(
+                "ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U",
+            )),
+            ..metadata
+        };
+        assert_eq!(metadata.check_urls(&policy()), Ok(()));
+    }
+
+    #[test]
+    fn every_field() {
+        let metadata = Metadata {
+            image: Some(RawUrl::from("http://assets.wanderers.ai/0.png")),
+            external_url: Some(Url::parse("https://notwanderers.ai/").unwrap()),
+            animation_url: Some(RawUrl::from("data:video/mp4;base64,AAAA")),
+            youtube_url: Some(Url::parse("https://www.youtube.com/watch?v=dQw4w9WgXcQ").unwrap()),
+            ..Default::default()
+        };
+        let violations = metadata.check_urls(&policy()).unwrap_err();
+        assert_eq!(
+            violations,
+            [
+                violation(
+                    "image",
+                    "http://assets.wanderers.ai/0.png",
+                    UrlRule::Scheme("http".into())
+                ),
+                violation(
+                    "external_url",
+                    "https://notwanderers.ai/",
+                    UrlRule::Host("notwanderers.ai".into())
+                ),
+                violation(
+                    "animation_url",
+                    "data:video/mp4;base64,AAAA",
+                    UrlRule::DataUri
+                ),
+                violation(
+                    "youtube_url",
+                    "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+                    UrlRule::Host("www.youtube.com".into())
+                ),
+            ]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "image `http://assets.wanderers.ai/0.png`: scheme `http` is not allowed"
+        );
+
+        let policy = policy()
+            .allow_data_uris(true)
+            .allow_host_suffix("youtube.com");
+        assert_eq!(
+            metadata.check_urls(&policy),
+            Err(vec![
+                violation(
+                    "image",
+                    "http://assets.wanderers.ai/0.png",
+                    UrlRule::Scheme("http".into())
+                ),
+                violation(
+                    "external_url",
+                    "https://notwanderers.ai/",
+                    UrlRule::Host("notwanderers.ai".into())
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn ipfs_gateways() {
+        let gateway = format!("https://ipfs.io/ipfs/{}/0.png", CID);
+        let metadata = Metadata {
+            image: Some(RawUrl(gateway.clone())),
+            ..Default::default()
+        };
+        assert_eq!(
+            metadata.check_urls(&policy()),
+            Err(vec![violation("image", &gateway, UrlRule::IpfsGateway)])
+        );
+        assert_eq!(
+            metadata.check_urls(&policy().allow_host_suffix("ipfs.io")),
+            Err(vec![violation("image", &gateway, UrlRule::IpfsGateway)])
+        );
+        assert_eq!(
+            metadata.check_urls(
+                &policy()
+                    .allow_ipfs_gateways(true)
+                    .allow_host_suffix("ipfs.io")
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn ipfs_gateway_hosts() {
+        let policy = policy().allow_ipfs_gateways(true);
+        assert_eq!(
+            policy.check(&format!("https://assets.wanderers.ai/ipfs/{}/0.png", CID)),
+            Ok(())
+        );
+        assert_eq!(
+            policy.check(&format!("https://ipfs.io/ipfs/{}/0.png", CID)),
+            Err(UrlRule::Host("ipfs.io".into()))
+        );
+        assert_eq!(
+            policy.check("https://attacker.example/ipfs/x"),
+            Err(UrlRule::Host("attacker.example".into()))
+        );
+    }
+
+    #[test]
+    fn subdomain_gateways() {
+        let policy = policy().allow_host_suffix("dweb.link");
+        let gateway = format!("https://{}.ipfs.dweb.link/0.png", CID);
+        assert_eq!(policy.check(&gateway), Err(UrlRule::IpfsGateway));
+        assert_eq!(
+            policy.clone().allow_ipfs_gateways(true).check(&gateway),
+            Ok(())
+        );
+
+        assert_eq!(
+            policy.check("https://assets.wanderers.ai/ipfs/docs/0.png"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn mixed_schemes() {
+        let policy = UrlPolicy::new().allow_scheme("https");
+        assert_eq!(policy.check("https://example.com/0.png"), Ok(()));
+        assert_eq!(
+            policy.check(&format!("ipfs://{}", CID)),
+            Err(UrlRule::Scheme("ipfs".into()))
+        );
+        assert_eq!(
+            policy.check("ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U"),
+            Err(UrlRule::Scheme("ar".into()))
+        );
+        assert_eq!(policy.check("DATA:,"), Err(UrlRule::DataUri));
+        assert_eq!(policy.check("0.png"), Err(UrlRule::Relative));
+        assert_eq!(policy.check("https://"), Err(UrlRule::Invalid));
+        assert_eq!(
+            UrlPolicy::new().check("https://example.com/"),
+            Err(UrlRule::Scheme("https".into()))
+        );
+    }
+}
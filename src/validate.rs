@@ -0,0 +1,823 @@
+use std::{fmt, ops::RangeInclusive};
+
+use crate::{
+    contract::MAX_BASIS_POINTS, media::extension, royalty::is_address, youtube, AttributeEntry,
+    AttributeValue, DisplayType, Metadata, ProfileLimits, RawUrl, TraitTypeComparison,
+};
+
+/// Timestamps with 13 digits, which are milliseconds for dates between 2001 and 2286.
+const MILLISECONDS: RangeInclusive<f64> = 1e12..=(1e13 - 1.0);
+
+impl Metadata {
+    /// Checks the metadata for common mistakes, returning every problem found.
+    ///
+    /// This is an opt-in check on top of what is enforced by the type itself, see [`ValidationError`]
+    /// for the problems that are reported.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        self.validate_with(&ProfileLimits::default())
+    }
+
+    /// Checks the metadata as [`validate`](Metadata::validate) does, along with the given `limits`.
+    pub(crate) fn validate_with(&self, limits: &ProfileLimits) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        if self.name.trim().is_empty() {
+            errors.push(ValidationError::EmptyName);
+        }
+        errors.extend(check_length("name", &self.name, limits.max_name_len));
+        if self.description.trim().is_empty() {
+            errors.push(ValidationError::EmptyDescription);
+        }
+        errors.extend(check_length(
+            "description",
+            &self.description,
+            limits.max_description_len,
+        ));
+        if self.image.is_none() && self.image_data.is_none() {
+            errors.push(ValidationError::MissingImage);
+        }
+        errors.extend(check_scheme("image", self.image.as_ref(), &limits.schemes));
+        errors.extend(check_scheme(
+            "animation_url",
+            self.animation_url.as_ref(),
+            &limits.schemes,
+        ));
+        if let (Some(formats), Some(extension)) = (
+            &limits.animation_formats,
+            self.animation_url.as_deref().and_then(extension),
+        ) {
+            if !formats
+                .iter()
+                .any(|format| format.eq_ignore_ascii_case(extension))
+            {
+                errors.push(ValidationError::UnsupportedAnimationFormat(
+                    extension.to_ascii_lowercase(),
+                ));
+            }
+        }
+        if self.youtube_url.is_some() && !limits.youtube_url {
+            errors.push(ValidationError::UnsupportedField("youtube_url"));
+        }
+        if let Some(youtube_url) = &self.youtube_url {
+            if !youtube::is_youtube(youtube_url) {
+                errors.push(ValidationError::NotYoutube(youtube_url.to_string()));
+            } else if youtube::video_id(youtube_url).is_none() {
+                errors.push(ValidationError::MissingVideoId(youtube_url.to_string()));
+            }
+        }
+
+        errors.extend(
+            self.duplicate_trait_type_indices(if limits.case_insensitive_trait_types {
+                TraitTypeComparison::CaseInsensitive
+            } else {
+                TraitTypeComparison::CaseSensitive
+            })
+            .into_iter()
+            .map(
+                |(trait_type, indices)| ValidationError::DuplicateTraitType {
+                    trait_type: trait_type.to_owned(),
+                    indices,
+                },
+            ),
+        );
+        errors.extend(
+            self.attributes
+                .iter()
+                .filter_map(|attribute| attribute.check_with(&limits.dates).err()),
+        );
+        if let Some(display_types) = &limits.display_types {
+            errors.extend(
+                self.attributes
+                    .iter()
+                    .filter_map(|attribute| Some((attribute, attribute.display_type.as_ref()?)))
+                    .filter(|(_, display_type)| !display_types.contains(display_type))
+                    .map(
+                        |(attribute, display_type)| ValidationError::UnsupportedDisplayType {
+                            trait_type: attribute.trait_type.clone(),
+                            display_type: display_type.clone(),
+                        },
+                    ),
+            );
+        }
+
+        if let Some(basis_points) = self.seller_fee_basis_points {
+            if basis_points > MAX_BASIS_POINTS {
+                errors.push(ValidationError::FeeTooHigh(basis_points));
+            }
+        }
+        let royalty = self.royalty_basis_points();
+        if royalty > MAX_BASIS_POINTS {
+            errors.push(ValidationError::FeeTooHigh(royalty));
+        }
+        errors.extend(
+            self.royalties
+                .iter()
+                .filter(|royalty| !is_address(&royalty.account))
+                .map(|royalty| ValidationError::InvalidAddress(royalty.account.clone())),
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Checks that a field is at most `max` characters long.
+fn check_length(field: &'static str, value: &str, max: Option<usize>) -> Option<ValidationError> {
+    let max = max?;
+    let len = value.chars().count();
+    (len > max).then_some(ValidationError::TooLong { field, len, max })
+}
+
+/// Checks that an absolute media URL uses one of the allowed `schemes`. Relative URLs are left to
+/// [`Metadata::resolve_urls`].
+fn check_scheme(
+    field: &'static str,
+    url: Option<&RawUrl>,
+    schemes: &[String],
+) -> Option<ValidationError> {
+    let url = url.filter(|url| !url.is_relative())?;
+    let scheme = url.split_once(':').map_or("", |(scheme, _)| scheme);
+    if schemes
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+    {
+        None
+    } else {
+        Some(ValidationError::UnsupportedScheme {
+            field,
+            url: url.to_string(),
+        })
+    }
+}
+
+impl AttributeEntry {
+    /// The default range of plausible [`Date`](DisplayType::Date) values, in seconds, from just after the Unix
+    /// epoch until the start of 2200.
+    pub const PLAUSIBLE_DATES: RangeInclusive<i64> = 1..=7_258_118_400;
+
+    /// Checks that a numerical attribute is in range for its display type, as part of [`Metadata::validate`].
+    ///
+    /// A [`BoostPercentage`](DisplayType::BoostPercentage) must be from 0 to 100, a
+    /// [`Number`](DisplayType::Number) or [`BoostNumber`](DisplayType::BoostNumber) must not exceed its
+    /// [`max_value`](AttributeEntry::max_value), and a [`Date`](DisplayType::Date) must be within
+    /// [`PLAUSIBLE_DATES`](AttributeEntry::PLAUSIBLE_DATES). Other attributes always pass.
+    pub fn check(&self) -> Result<(), ValidationError> {
+        self.check_with(&Self::PLAUSIBLE_DATES)
+    }
+
+    /// Checks the attribute as [`check`](AttributeEntry::check) does, with dates limited to `dates`.
+    pub(crate) fn check_with(&self, dates: &RangeInclusive<i64>) -> Result<(), ValidationError> {
+        let value = match self.value {
+            AttributeValue::Integer(value) => value as f64,
+            AttributeValue::Float(value) => value.0,
+            _ => return Ok(()),
+        };
+        let trait_type = self.trait_type.clone();
+        match (&self.display_type, self.max_value) {
+            (Some(DisplayType::BoostPercentage), _) if !(0.0..=100.0).contains(&value) => {
+                Err(ValidationError::BoostPercentageOutOfRange {
+                    trait_type,
+                    value: self.value.clone(),
+                })
+            }
+            (Some(DisplayType::Number | DisplayType::BoostNumber), Some(max_value))
+                if value > max_value as f64 =>
+            {
+                Err(ValidationError::ExceedsMaxValue {
+                    trait_type,
+                    value: self.value.clone(),
+                    max_value,
+                })
+            }
+            (Some(DisplayType::Date), _) => {
+                let in_range =
+                    |value: f64| (*dates.start() as f64..=*dates.end() as f64).contains(&value);
+                if in_range(value) {
+                    Ok(())
+                } else if MILLISECONDS.contains(&value) && in_range(value / 1000.0) {
+                    Err(ValidationError::DateInMilliseconds {
+                        trait_type,
+                        value: self.value.clone(),
+                    })
+                } else {
+                    Err(ValidationError::DateOutOfRange {
+                        trait_type,
+                        value: self.value.clone(),
+                    })
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Converts a [`Date`](DisplayType::Date) value with 13 digits from milliseconds into seconds, returning the
+    /// original value if it was changed.
+    ///
+    /// Values that are a whole number of seconds stay integers, while others become floats. This is opt-in, as
+    /// [`Metadata::validate`] only reports such values.
+    pub fn normalize_date_millis(&mut self) -> Option<AttributeValue> {
+        if self.display_type != Some(DisplayType::Date) {
+            return None;
+        }
+        let seconds = match self.value {
+            AttributeValue::Integer(millis) if MILLISECONDS.contains(&(millis as f64)) => {
+                if millis % 1000 == 0 {
+                    AttributeValue::Integer(millis / 1000)
+                } else {
+                    AttributeValue::from(millis as f64 / 1000.0)
+                }
+            }
+            AttributeValue::Float(millis) if MILLISECONDS.contains(&millis.0) => {
+                AttributeValue::from(millis.0 / 1000.0)
+            }
+            _ => return None,
+        };
+        Some(std::mem::replace(&mut self.value, seconds))
+    }
+}
+
+/// A problem found by [`Metadata::validate`] and the other validation methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The name is empty.
+    EmptyName,
+    /// The description is empty.
+    EmptyDescription,
+    /// Neither an image URL nor image data is set.
+    MissingImage,
+    /// A field is longer than allowed, in characters.
+    TooLong {
+        /// Name of the field, such as `"name"`.
+        field: &'static str,
+        /// Length of the field.
+        len: usize,
+        /// Maximum length of the field.
+        max: usize,
+    },
+    /// A media URL uses a scheme that is not allowed, which by default is anything other than `http`, `https`,
+    /// `ipfs`, `ar` or `data`.
+    UnsupportedScheme {
+        /// Name of the field, such as `"image"`.
+        field: &'static str,
+        /// The offending URL.
+        url: String,
+    },
+    /// The animation has a file extension that the marketplace does not display.
+    UnsupportedAnimationFormat(String),
+    /// A field is set that the marketplace ignores.
+    UnsupportedField(&'static str),
+    /// The YouTube URL is not hosted on YouTube.
+    NotYoutube(String),
+    /// The YouTube URL does not link to a video. See [`Metadata::youtube_video_id`].
+    MissingVideoId(String),
+    /// A [`BoostPercentage`](DisplayType::BoostPercentage) attribute is outside the range 0 to 100.
+    BoostPercentageOutOfRange {
+        /// Trait type of the attribute.
+        trait_type: Option<String>,
+        /// The offending value.
+        value: AttributeValue,
+    },
+    /// A numerical attribute exceeds its `max_value`.
+    ExceedsMaxValue {
+        /// Trait type of the attribute.
+        trait_type: Option<String>,
+        /// The offending value.
+        value: AttributeValue,
+        /// Maximum value of the attribute.
+        max_value: i64,
+    },
+    /// An attribute has a display type that is not supported.
+    UnsupportedDisplayType {
+        /// Trait type of the attribute.
+        trait_type: Option<String>,
+        /// The offending display type.
+        display_type: DisplayType,
+    },
+    /// A [`Date`](DisplayType::Date) attribute is outside the range of plausible dates.
+    DateOutOfRange {
+        /// Trait type of the attribute.
+        trait_type: Option<String>,
+        /// The offending value.
+        value: AttributeValue,
+    },
+    /// A [`Date`](DisplayType::Date) attribute appears to be in milliseconds rather than seconds. See
+    /// [`AttributeEntry::normalize_date_millis`].
+    DateInMilliseconds {
+        /// Trait type of the attribute.
+        trait_type: Option<String>,
+        /// The offending value.
+        value: AttributeValue,
+    },
+    /// More than one attribute has the given trait type. See [`Metadata::dedup_attributes`].
+    DuplicateTraitType {
+        /// The duplicated trait type, as spelled by its first attribute.
+        trait_type: String,
+        /// Indices of the attributes with the trait type.
+        indices: Vec<usize>,
+    },
+    /// A localization URI does not contain the `{locale}` placeholder.
+    MissingLocalePlaceholder,
+    /// The default locale of a localization is not in its list of locales.
+    UnlistedDefaultLocale(String),
+    /// A fee exceeds 10000 basis points.
+    FeeTooHigh(u32),
+    /// A fee is set without an address to pay it to.
+    MissingFeeRecipient,
+    /// An account is not a `0x`-prefixed 20-byte hexadecimal address.
+    InvalidAddress(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::EmptyName => write!(f, "name is empty"),
+            ValidationError::EmptyDescription => write!(f, "description is empty"),
+            ValidationError::MissingImage => write!(f, "neither image nor image_data is set"),
+            ValidationError::TooLong { field, len, max } => {
+                write!(f, "{} is {} characters long, exceeding {}", field, len, max)
+            }
+            ValidationError::UnsupportedScheme { field, url } => {
+                write!(f, "{} `{}` has an unsupported scheme", field, url)
+            }
+            ValidationError::UnsupportedAnimationFormat(extension) => {
+                write!(f, "animation format `{}` is not supported", extension)
+            }
+            ValidationError::UnsupportedField(field) => write!(f, "{} is not supported", field),
+            ValidationError::NotYoutube(url) => {
+                write!(f, "youtube_url `{}` is not a YouTube URL", url)
+            }
+            ValidationError::MissingVideoId(url) => {
+                write!(f, "youtube_url `{}` does not link to a video", url)
+            }
+            ValidationError::BoostPercentageOutOfRange { trait_type, value } => write!(
+                f,
+                "boost percentage {} of {} is outside 0 to 100",
+                value,
+                Attribute(trait_type)
+            ),
+            ValidationError::ExceedsMaxValue {
+                trait_type,
+                value,
+                max_value,
+            } => write!(
+                f,
+                "value {} of {} exceeds its max_value of {}",
+                value,
+                Attribute(trait_type),
+                max_value
+            ),
+            ValidationError::UnsupportedDisplayType {
+                trait_type,
+                display_type,
+            } => write!(
+                f,
+                "display type `{}` of {} is not supported",
+                display_type.as_str(),
+                Attribute(trait_type)
+            ),
+            ValidationError::DateOutOfRange { trait_type, value } => write!(
+                f,
+                "date {} of {} is outside the range of plausible dates",
+                value,
+                Attribute(trait_type)
+            ),
+            ValidationError::DateInMilliseconds { trait_type, value } => write!(
+                f,
+                "date {} of {} appears to be in milliseconds rather than seconds",
+                value,
+                Attribute(trait_type)
+            ),
+            ValidationError::DuplicateTraitType {
+                trait_type,
+                indices,
+            } => {
+                write!(
+                    f,
+                    "trait type `{}` appears more than once, on attributes",
+                    trait_type
+                )?;
+                for index in indices {
+                    write!(f, " {}", index)?;
+                }
+                Ok(())
+            }
+            ValidationError::MissingLocalePlaceholder => {
+                write!(f, "localization URI does not contain `{{locale}}`")
+            }
+            ValidationError::UnlistedDefaultLocale(locale) => {
+                write!(
+                    f,
+                    "default locale `{}` is not in the list of locales",
+                    locale
+                )
+            }
+            ValidationError::FeeTooHigh(basis_points) => {
+                write!(f, "fee of {} basis points exceeds 10000", basis_points)
+            }
+            ValidationError::MissingFeeRecipient => write!(f, "fee is set without a fee recipient"),
+            ValidationError::InvalidAddress(account) => {
+                write!(f, "`{}` is not a valid address", account)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Formats an attribute by its trait type, for use in error messages.
+struct Attribute<'a>(&'a Option<String>);
+
+impl fmt::Display for Attribute<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(trait_type) => write!(f, "attribute `{}`", trait_type),
+            None => write!(f, "a generic attribute"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::{
+        AttributeEntry, AttributeValue, DedupStrategy, DisplayType, Metadata, ProfileLimits,
+        RawUrl, TraitTypeComparison, ValidationError, ValidationProfile,
+    };
+
+    fn metadata() -> Metadata {
+        Metadata {
+            attributes: vec![
+                AttributeEntry::string("Core", "Vortex"),
+                AttributeEntry::number("Level", 3),
+            ],
+            ..crate::fixtures::metadata()
+        }
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(metadata().validate(), Ok(()));
+    }
+
+    #[test]
+    fn invalid() {
+        let mut metadata = metadata();
+        metadata.name = String::new();
+        metadata.description = " ".into();
+        metadata.attributes.extend([
+            AttributeEntry::string("Core", "Nebula"),
+            AttributeEntry::number("Core", 1),
+            AttributeEntry::bool("Level", true),
+        ]);
+
+        assert_eq!(
+            metadata.validate(),
+            Err(vec![
+                ValidationError::EmptyName,
+                ValidationError::EmptyDescription,
+                ValidationError::DuplicateTraitType {
+                    trait_type: "Core".into(),
+                    indices: vec![0, 2, 3],
+                },
+                ValidationError::DuplicateTraitType {
+                    trait_type: "Level".into(),
+                    indices: vec![1, 4],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn default_is_invalid() {
+        assert_eq!(
+            Metadata::default().validate(),
+            Err(vec![
+                ValidationError::EmptyName,
+                ValidationError::EmptyDescription,
+            ])
+        );
+
+        assert_eq!(crate::fixtures::metadata().validate(), Ok(()));
+    }
+
+    #[test]
+    fn duplicate_trait_types() {
+        let mut metadata = metadata();
+        metadata.attributes.extend([
+            AttributeEntry::string("Core", "Nebula"),
+            AttributeEntry::number("Core", 1),
+            AttributeEntry::string("core", "Vortex"),
+        ]);
+        assert_eq!(metadata.duplicate_trait_types(), ["Core"]);
+        assert_eq!(
+            metadata.duplicate_trait_type_indices(TraitTypeComparison::CaseSensitive),
+            [("Core", vec![0, 2, 3])]
+        );
+        assert_eq!(
+            metadata.duplicate_trait_type_indices(TraitTypeComparison::CaseInsensitive),
+            [("Core", vec![0, 2, 3, 4])]
+        );
+
+        let error = ValidationError::DuplicateTraitType {
+            trait_type: "Core".into(),
+            indices: vec![0, 2],
+        };
+        assert_eq!(
+            error.to_string(),
+            "trait type `Core` appears more than once, on attributes 0 2"
+        );
+    }
+
+    #[test]
+    fn case_insensitive_trait_types() {
+        let mut metadata = metadata();
+        metadata.attributes.extend([
+            AttributeEntry::string("Background", "Blue"),
+            AttributeEntry::string("background", "Red"),
+        ]);
+        assert_eq!(metadata.validate(), Ok(()));
+
+        let limits = ProfileLimits {
+            case_insensitive_trait_types: true,
+            ..ProfileLimits::default()
+        };
+        assert_eq!(
+            metadata.validate_for(&ValidationProfile::Custom(limits)),
+            Err(vec![ValidationError::DuplicateTraitType {
+                trait_type: "Background".into(),
+                indices: vec![2, 3],
+            }])
+        );
+    }
+
+    #[test]
+    fn dedup_attributes() {
+        let mut metadata = metadata();
+        metadata.attributes.extend([
+            AttributeEntry::string("Background", "Blue"),
+            AttributeEntry::generic("Generic"),
+            AttributeEntry::string("Background", "Red"),
+            AttributeEntry::generic("Generic"),
+        ]);
+
+        let mut first = metadata.clone();
+        assert_eq!(
+            first.dedup_attributes(DedupStrategy::KeepFirst, TraitTypeComparison::CaseSensitive),
+            1
+        );
+        assert_eq!(
+            first.attributes[2..],
+            [
+                AttributeEntry::string("Background", "Blue"),
+                AttributeEntry::generic("Generic"),
+                AttributeEntry::generic("Generic"),
+            ]
+        );
+        assert_eq!(first.validate(), Ok(()));
+
+        let mut insensitive = metadata.clone();
+        insensitive
+            .attributes
+            .push(AttributeEntry::string("background", "Green"));
+        assert_eq!(
+            insensitive.dedup_attributes(
+                DedupStrategy::KeepFirst,
+                TraitTypeComparison::CaseInsensitive
+            ),
+            2
+        );
+        assert_eq!(insensitive.attributes[2..], first.attributes[2..]);
+
+        assert_eq!(
+            metadata.dedup_attributes(DedupStrategy::KeepLast, TraitTypeComparison::CaseSensitive),
+            1
+        );
+        assert_eq!(
+            metadata.attributes[2..],
+            [
+                AttributeEntry::generic("Generic"),
+                AttributeEntry::string("Background", "Red"),
+                AttributeEntry::generic("Generic"),
+            ]
+        );
+        assert_eq!(
+            metadata.dedup_attributes(DedupStrategy::KeepLast, TraitTypeComparison::CaseSensitive),
+            0
+        );
+    }
+
+    #[test]
+    fn urls() {
+        let mut metadata = metadata();
+        metadata.animation_url = Some(RawUrl::from("ftp://assets.wanderers.ai/0.mp4"));
+        metadata.youtube_url = Some(Url::parse("https://vimeo.com/76979871").unwrap());
+        assert_eq!(
+            metadata.validate(),
+            Err(vec![
+                ValidationError::UnsupportedScheme {
+                    field: "animation_url",
+                    url: "ftp://assets.wanderers.ai/0.mp4".into(),
+                },
+                ValidationError::NotYoutube("https://vimeo.com/76979871".into()),
+            ])
+        );
+
+        for image in [
+            "ipfs://QmSqC7TfyQc8wgxa8uBwzoJCmWyLbzxBx8d35pKhqWzYyV/0.png",
+            "0.png",
+        ] {
+            metadata.image = Some(RawUrl::from(image));
+            metadata.animation_url = None;
+            metadata.youtube_url =
+                Some(Url::parse("https://www.youtube.com/watch?v=dQw4w9WgXcQ").unwrap());
+            assert_eq!(metadata.validate(), Ok(()));
+        }
+        metadata.youtube_url =
+            Some(Url::parse("https://notyoutube.com/watch?v=dQw4w9WgXcQ").unwrap());
+        assert_eq!(
+            metadata.validate(),
+            Err(vec![ValidationError::NotYoutube(
+                "https://notyoutube.com/watch?v=dQw4w9WgXcQ".into()
+            )])
+        );
+        metadata.youtube_url = Some(Url::parse("https://www.youtube.com/watch?list=PL1").unwrap());
+        assert_eq!(
+            metadata.validate(),
+            Err(vec![ValidationError::MissingVideoId(
+                "https://www.youtube.com/watch?list=PL1".into()
+            )])
+        );
+    }
+
+    #[test]
+    fn numerical_attributes() {
+        let mut metadata = metadata();
+        metadata.attributes.extend([
+            AttributeEntry {
+                display_type: Some(DisplayType::BoostPercentage),
+                ..AttributeEntry::number("Stamina Increase", 150)
+            },
+            AttributeEntry {
+                display_type: Some(DisplayType::BoostPercentage),
+                ..AttributeEntry::float("Speed Increase", 12.5)
+            },
+            AttributeEntry::date("birthday", 1_546_360_800_000),
+            AttributeEntry::date("anniversary", 1_546_360_800),
+        ]);
+
+        let errors = metadata.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                ValidationError::BoostPercentageOutOfRange {
+                    trait_type: Some("Stamina Increase".into()),
+                    value: AttributeValue::Integer(150),
+                },
+                ValidationError::DateInMilliseconds {
+                    trait_type: Some("birthday".into()),
+                    value: AttributeValue::Integer(1_546_360_800_000),
+                },
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "boost percentage 150 of attribute `Stamina Increase` is outside 0 to 100"
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            "date 1546360800000 of attribute `birthday` appears to be in milliseconds rather than seconds"
+        );
+    }
+
+    #[test]
+    fn boost_percentage_boundaries() {
+        for value in [0, 100] {
+            assert_eq!(
+                AttributeEntry::boost_percentage("Stamina Increase", value).check(),
+                Ok(())
+            );
+        }
+        for value in [-1, 101] {
+            assert_eq!(
+                AttributeEntry::boost_percentage("Stamina Increase", value).check(),
+                Err(ValidationError::BoostPercentageOutOfRange {
+                    trait_type: Some("Stamina Increase".into()),
+                    value: AttributeValue::Integer(value),
+                })
+            );
+        }
+        let attribute = AttributeEntry {
+            display_type: Some(DisplayType::BoostPercentage),
+            ..AttributeEntry::float("Stamina Increase", 100.5)
+        };
+        assert!(attribute.check().is_err());
+    }
+
+    #[test]
+    fn max_value() {
+        assert_eq!(
+            AttributeEntry::number_with_max("Level", 5, 5).check(),
+            Ok(())
+        );
+        assert_eq!(
+            AttributeEntry::number_with_max("Level", 6, 5).check(),
+            Err(ValidationError::ExceedsMaxValue {
+                trait_type: Some("Level".into()),
+                value: AttributeValue::Integer(6),
+                max_value: 5,
+            })
+        );
+
+        let attribute = AttributeEntry {
+            max_value: Some(40),
+            ..AttributeEntry::boost_number("Aqua Power", 41)
+        };
+        let error = attribute.check().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "value 41 of attribute `Aqua Power` exceeds its max_value of 40"
+        );
+
+        let attribute = AttributeEntry {
+            max_value: Some(5),
+            ..AttributeEntry::new("Level", 6)
+        };
+        assert_eq!(attribute.check(), Ok(()));
+
+        let mut metadata = metadata();
+        metadata
+            .attributes
+            .push(AttributeEntry::number_with_max("Generation", 3, 2));
+        assert_eq!(
+            metadata.validate(),
+            Err(vec![ValidationError::ExceedsMaxValue {
+                trait_type: Some("Generation".into()),
+                value: AttributeValue::Integer(3),
+                max_value: 2,
+            }])
+        );
+    }
+
+    #[test]
+    fn dates() {
+        let seconds = AttributeEntry::date("Birthday", 1_546_360_800);
+        assert_eq!(seconds.check(), Ok(()));
+
+        let mut millis = AttributeEntry::date("Birthday", 1_546_360_800_000);
+        assert_eq!(
+            millis.check(),
+            Err(ValidationError::DateInMilliseconds {
+                trait_type: Some("Birthday".into()),
+                value: AttributeValue::Integer(1_546_360_800_000),
+            })
+        );
+        assert_eq!(
+            millis.normalize_date_millis(),
+            Some(AttributeValue::Integer(1_546_360_800_000))
+        );
+        assert_eq!(millis, seconds);
+        assert_eq!(millis.normalize_date_millis(), None);
+
+        let mut millis = AttributeEntry::date("Birthday", 1_546_360_800_500);
+        millis.normalize_date_millis();
+        assert_eq!(millis.value, AttributeValue::from(1_546_360_800.5));
+        assert_eq!(
+            AttributeEntry::number("Level", 1_546_360_800_000).normalize_date_millis(),
+            None
+        );
+
+        for value in [0, -1, 7_258_118_401, 1_546_360_800_000_000] {
+            assert_eq!(
+                AttributeEntry::date("Birthday", value).check(),
+                Err(ValidationError::DateOutOfRange {
+                    trait_type: Some("Birthday".into()),
+                    value: AttributeValue::Integer(value),
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn custom_date_range() {
+        let mut metadata = metadata();
+        metadata
+            .attributes
+            .push(AttributeEntry::date("Birthday", 1_546_360_800));
+        let limits = ProfileLimits {
+            dates: 1_577_836_800..=1_893_456_000,
+            ..ProfileLimits::default()
+        };
+        assert_eq!(metadata.validate(), Ok(()));
+        assert_eq!(
+            metadata.validate_for(&ValidationProfile::Custom(limits)),
+            Err(vec![ValidationError::DateOutOfRange {
+                trait_type: Some("Birthday".into()),
+                value: AttributeValue::Integer(1_546_360_800),
+            }])
+        );
+    }
+}
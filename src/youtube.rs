@@ -0,0 +1,112 @@
+use url::Url;
+
+use crate::Metadata;
+
+/// Hosts of YouTube URLs, along with their subdomains.
+const HOSTS: &[&str] = &["youtube.com", "youtu.be", "youtube-nocookie.com"];
+
+/// Checks that `url` is hosted on YouTube, such as on `www.youtube.com` or `youtu.be`.
+pub(crate) fn is_youtube(url: &Url) -> bool {
+    url.host_str().is_some_and(|host| {
+        HOSTS.iter().any(|youtube| {
+            host == *youtube
+                || host
+                    .strip_suffix(youtube)
+                    .is_some_and(|subdomain| subdomain.ends_with('.'))
+        })
+    })
+}
+
+/// Checks that `id` is 11 characters of the URL-safe base64 alphabet, as YouTube video IDs are.
+fn is_video_id(id: &str) -> bool {
+    id.len() == 11
+        && id
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_')
+}
+
+/// Returns the video ID of a `youtube.com/watch?v=<id>`, `youtube.com/embed/<id>`, `youtube.com/shorts/<id>`
+/// or `youtu.be/<id>` URL.
+pub(crate) fn video_id(url: &Url) -> Option<String> {
+    if !is_youtube(url) {
+        return None;
+    }
+    let segments = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let id = match (url.host_str()?.ends_with("youtu.be"), &segments[..]) {
+        (true, [id]) => (*id).to_owned(),
+        (false, ["watch"]) => url
+            .query_pairs()
+            .find(|(key, _)| key == "v")
+            .map(|(_, id)| id.into_owned())?,
+        (false, ["embed" | "shorts", id]) => (*id).to_owned(),
+        _ => return None,
+    };
+    is_video_id(&id).then_some(id)
+}
+
+impl Metadata {
+    /// Returns the 11-character ID of the video at [`youtube_url`](Metadata::youtube_url), such as
+    /// `"dQw4w9WgXcQ"`.
+    ///
+    /// `youtube.com/watch?v=<id>`, `youtube.com/embed/<id>`, `youtube.com/shorts/<id>` and `youtu.be/<id>` URLs
+    /// are recognized, with any other query parameters and trailing slashes. Returns `None` for any other URL.
+    pub fn youtube_video_id(&self) -> Option<String> {
+        video_id(self.youtube_url.as_ref()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::Metadata;
+
+    fn youtube(url: &str) -> Metadata {
+        Metadata {
+            youtube_url: Some(Url::parse(url).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn video_id() {
+        for url in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://youtube.com/watch/?t=42&v=dQw4w9WgXcQ&list=PL1",
+            "https://m.youtube.com/watch?v=dQw4w9WgXcQ#t=1",
+            "https://www.youtube.com/embed/dQw4w9WgXcQ",
+            "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ/?autoplay=1",
+            "https://www.youtube.com/shorts/dQw4w9WgXcQ",
+            "https://youtu.be/dQw4w9WgXcQ",
+            "https://youtu.be/dQw4w9WgXcQ/?si=abc",
+        ] {
+            assert_eq!(
+                youtube(url).youtube_video_id().as_deref(),
+                Some("dQw4w9WgXcQ"),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn no_video_id() {
+        for url in [
+            "https://vimeo.com/76979871",
+            "https://notyoutube.com/watch?v=dQw4w9WgXcQ",
+            "https://www.youtube.com/watch",
+            "https://www.youtube.com/watch?v=dQw4w9WgXc",
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQQ",
+            "https://www.youtube.com/watch?v=dQw4w9WgX!Q",
+            "https://www.youtube.com/embed/",
+            "https://www.youtube.com/channel/UCuAXFkgsw1L7xaCfnd5JJOw",
+            "https://youtu.be/",
+        ] {
+            assert_eq!(youtube(url).youtube_video_id(), None, "{}", url);
+        }
+        assert_eq!(Metadata::default().youtube_video_id(), None);
+    }
+}